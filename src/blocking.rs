@@ -0,0 +1,234 @@
+//! A blocking alternative to the asynchronous [`Client`](crate::Client).
+//!
+//! This requires the `blocking` feature. Like [`reqwest::blocking`], it must
+//! not be used from within an asynchronous runtime.
+//!
+//! ```no_run
+//! use buy_me_a_coffee::MemberStatus;
+//!
+//! let client = buy_me_a_coffee::blocking::Client::new("personal access token here");
+//! let page = client.members(MemberStatus::Active, 1).unwrap();
+//! ```
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use reqwest::blocking::{Request, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    mask, page_params,
+    pages::LastPages,
+    parse_response,
+    rate_limit::RateLimiter,
+    received,
+    retry::{Backoff, Retry},
+    sending, Authorize, ClientBuilder, MemberStatus, Membership, Page, Purchase, Result,
+    ServerErrorKind, Support,
+};
+
+/// Makes blocking requests to the API.
+///
+/// See [`crate::Client`] for details on each method.
+#[derive(Clone)]
+pub struct Client {
+    pub(crate) client: reqwest::blocking::Client,
+    pub(crate) token: String,
+    pub(crate) base_url: String,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) retry: Option<Retry>,
+    pub(crate) user_agent: String,
+    pub(crate) per_page: Option<u16>,
+    pub(crate) treat_html_as_unauthorized: bool,
+    pub(crate) untyped_fallback: bool,
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    pub(crate) last_pages: Arc<LastPages>,
+}
+
+impl Debug for Client {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("client", &self.client)
+            .field("token", &mask(&self.token))
+            .field("base_url", &self.base_url)
+            .field("timeout", &self.timeout)
+            .field("retry", &self.retry)
+            .field("user_agent", &self.user_agent)
+            .field("per_page", &self.per_page)
+            .field(
+                "treat_html_as_unauthorized",
+                &self.treat_html_as_unauthorized,
+            )
+            .field("untyped_fallback", &self.untyped_fallback)
+            .field("rate_limiter", &self.rate_limiter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Client {
+    /// # Panics
+    ///
+    /// Panics if a TLS backend cannot be initialized, or if called from within
+    /// an asynchronous runtime, like [`reqwest::blocking::Client::new`]. Like
+    /// [`crate::Client::new`], the token isn't checked.
+    pub fn new(token: impl ToString) -> Self {
+        Self::builder(token)
+            .build_blocking_unvalidated()
+            .expect("client should be valid")
+    }
+
+    /// Returns a [`ClientBuilder`] for configuring a client beyond just its
+    /// token. Finish it with [`ClientBuilder::build_blocking`].
+    pub fn builder(token: impl ToString) -> ClientBuilder {
+        ClientBuilder::new(token)
+    }
+
+    fn get<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        modify_request: impl FnOnce(RequestBuilder) -> RequestBuilder,
+    ) -> Result<T> {
+        let request = self
+            .client
+            .get(format!("{}{endpoint}", self.base_url))
+            .authorize(&self.token, &self.user_agent, self.timeout);
+        let request = modify_request(request).build()?;
+
+        #[cfg(feature = "tracing")]
+        let span = crate::request_span(endpoint, request.url());
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+
+        let result = self.receive(request);
+
+        #[cfg(feature = "tracing")]
+        crate::failed(&result);
+
+        result
+    }
+
+    fn receive<T: DeserializeOwned>(&self, request: Request) -> Result<T> {
+        let url = request.url().clone();
+
+        sending(&url, &self.token);
+
+        let response = self.send(request)?;
+        let status = response.status();
+
+        received(&url, status);
+
+        let headers = response.headers().clone();
+
+        parse_response(
+            status,
+            &headers,
+            &response.bytes()?,
+            self.treat_html_as_unauthorized,
+            self.untyped_fallback,
+        )
+    }
+
+    /// Adds the query parameters selecting `page` of a listing, and its size
+    /// if one was set with [`ClientBuilder::per_page`].
+    fn page_query(&self, request: RequestBuilder, page: u16) -> RequestBuilder {
+        request.query(&page_params(page, self.per_page))
+    }
+
+    /// Sends `request`, retrying it like [`crate::Client`] does.
+    fn send(&self, request: Request) -> reqwest::Result<Response> {
+        let mut backoff = Backoff::default();
+
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                thread::sleep(rate_limiter.reserve());
+            }
+
+            let result = self.client.execute(
+                request
+                    .try_clone()
+                    .expect("GET requests should not have a streamed body"),
+            );
+            let delay = backoff.next(
+                self.retry,
+                result
+                    .as_ref()
+                    .map(|response| (response.status(), response.headers())),
+            );
+
+            let Some(delay) = delay else {
+                return result;
+            };
+
+            thread::sleep(delay);
+        }
+    }
+
+    /// Returns all members.
+    pub fn members(&self, status: MemberStatus, page: u16) -> Result<Page<Membership>> {
+        self.last_pages.check(
+            format!("/v1/subscriptions?status={status}"),
+            self.members_raw(status, page),
+            ServerErrorKind::NoSubscriptions,
+            page,
+        )
+    }
+
+    /// Like [`Client::members`], but returns the API's error if there are no
+    /// members.
+    pub fn members_raw(&self, status: MemberStatus, page: u16) -> Result<Page<Membership>> {
+        self.get("/v1/subscriptions", |request| {
+            self.page_query(request.query(&[("status", status)]), page)
+        })
+    }
+
+    /// Returns membership details for the ID provided.
+    pub fn membership(&self, id: u32) -> Result<Membership> {
+        self.get(&format!("/v1/subscriptions/{id}"), |request| request)
+    }
+
+    /// Returns all onetime-supporters.
+    pub fn supporters(&self, page: u16) -> Result<Page<Support>> {
+        self.last_pages.check(
+            "/v1/supporters".to_string(),
+            self.supporters_raw(page),
+            ServerErrorKind::NoSupporters,
+            page,
+        )
+    }
+
+    /// Like [`Client::supporters`], but returns the API's error if there are
+    /// no supporters.
+    pub fn supporters_raw(&self, page: u16) -> Result<Page<Support>> {
+        self.get("/v1/supporters", |request| self.page_query(request, page))
+    }
+
+    /// Returns details on support with the ID provided.
+    pub fn support(&self, id: u32) -> Result<Support> {
+        self.get(&format!("/v1/supporters/{id}"), |request| request)
+    }
+
+    /// Returns all extra purchases.
+    pub fn extras(&self, page: u16) -> Result<Page<Purchase>> {
+        self.last_pages.check(
+            "/v1/extras".to_string(),
+            self.extras_raw(page),
+            ServerErrorKind::NoExtraPurchases,
+            page,
+        )
+    }
+
+    /// Like [`Client::extras`], but returns the API's error if there are no
+    /// extra purchases.
+    pub fn extras_raw(&self, page: u16) -> Result<Page<Purchase>> {
+        self.get("/v1/extras", |request| self.page_query(request, page))
+    }
+
+    /// Returns details on an extra purchase with the ID provided.
+    pub fn extra(&self, id: u32) -> Result<Purchase> {
+        self.get(&format!("/v1/extras/{id}"), |request| request)
+    }
+}
@@ -0,0 +1,477 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+    time::Duration,
+};
+
+use reqwest::{header::HeaderValue, Url};
+
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+use crate::blocking;
+#[cfg(feature = "cache")]
+use crate::cache::Cache;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::rate_limit::RateLimiter;
+use crate::{
+    mask, retry::Retry, Client, Error, HttpTransport, Inner, Result, PREFIX, USER_AGENT_VALUE,
+};
+
+const DEFAULT_MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
+
+/// Configures and creates a [`Client`].
+///
+/// Returned by [`Client::builder`].
+#[derive(Clone)]
+pub struct ClientBuilder {
+    token: String,
+    base_url: String,
+    client: Option<reqwest::Client>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    tcp_keepalive: Option<Duration>,
+    proxies: Vec<reqwest::Proxy>,
+    retry: Option<(u32, Duration)>,
+    max_retry_wait: Duration,
+    rate_limit: Option<u32>,
+    user_agent: String,
+    per_page: Option<u16>,
+    treat_html_as_unauthorized: bool,
+    untyped_fallback: bool,
+    #[cfg(feature = "cache")]
+    cache_ttl: Option<Duration>,
+}
+
+impl Debug for ClientBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("ClientBuilder");
+
+        debug_struct
+            .field("token", &mask(&self.token))
+            .field("base_url", &self.base_url)
+            .field("client", &self.client)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("tcp_keepalive", &self.tcp_keepalive)
+            .field("proxies", &self.proxies)
+            .field("retry", &self.retry)
+            .field("max_retry_wait", &self.max_retry_wait)
+            .field("rate_limit", &self.rate_limit)
+            .field("user_agent", &self.user_agent)
+            .field("per_page", &self.per_page)
+            .field(
+                "treat_html_as_unauthorized",
+                &self.treat_html_as_unauthorized,
+            )
+            .field("untyped_fallback", &self.untyped_fallback);
+
+        #[cfg(feature = "cache")]
+        debug_struct.field("cache_ttl", &self.cache_ttl);
+
+        debug_struct.finish_non_exhaustive()
+    }
+}
+
+impl ClientBuilder {
+    pub(crate) fn new(token: impl ToString) -> Self {
+        Self {
+            token: token.to_string(),
+            base_url: PREFIX.to_string(),
+            client: None,
+            transport: None,
+            timeout: None,
+            connect_timeout: None,
+            pool_idle_timeout: None,
+            tcp_keepalive: None,
+            proxies: Vec::new(),
+            retry: None,
+            max_retry_wait: DEFAULT_MAX_RETRY_WAIT,
+            rate_limit: None,
+            user_agent: USER_AGENT_VALUE.to_string(),
+            per_page: None,
+            treat_html_as_unauthorized: true,
+            untyped_fallback: false,
+            #[cfg(feature = "cache")]
+            cache_ttl: None,
+        }
+    }
+
+    /// Sets the URL that endpoints are appended to.
+    ///
+    /// Defaults to `https://developers.buymeacoffee.com/api`. Mostly useful
+    /// for pointing the client at a mock server, e.g. `http://localhost:8080`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the [`reqwest::Client`] used to send requests.
+    ///
+    /// Useful for sharing one connection pool across an application, or for
+    /// using a client configured in ways this builder doesn't support. By
+    /// default, a new [`reqwest::Client`] is created.
+    ///
+    /// The default client accepts gzip and brotli compressed responses, which
+    /// makes listing every supporter of a big account much lighter. A client
+    /// provided here only does so if reqwest's `gzip` and `brotli` features
+    /// are enabled and it hasn't been configured otherwise.
+    pub fn reqwest_client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Sets the [`HttpTransport`] used to send requests, replacing the
+    /// [`reqwest::Client`].
+    ///
+    /// Mostly useful for testing code that uses a [`Client`] without a server.
+    /// The blocking client ignores this.
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Sets how long a request may take in total, from connecting until the
+    /// response body has been read, before failing with [`Error::Reqwest`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long connecting to the server may take before failing with
+    /// [`Error::Reqwest`].
+    ///
+    /// This has no effect if a client was provided with
+    /// [`ClientBuilder::reqwest_client`], configure that client instead. It
+    /// also has no effect on WebAssembly, where the browser connects.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Sets how long an idle connection is kept open for reuse before being
+    /// closed.
+    ///
+    /// Defaults to reqwest's own default of 90 seconds. Lowering it keeps
+    /// long-lived processes that poll the API from reusing connections the
+    /// server or a proxy has already dropped. Like
+    /// [`ClientBuilder::connect_timeout`], this has no effect if a client was
+    /// provided with [`ClientBuilder::reqwest_client`] or on WebAssembly.
+    pub fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(pool_idle_timeout);
+        self
+    }
+
+    /// Sets how long a connection may be idle before TCP keepalive probes are
+    /// sent to check that it's still alive.
+    ///
+    /// Defaults to reqwest's own default of 15 seconds. Like
+    /// [`ClientBuilder::connect_timeout`], this has no effect if a client was
+    /// provided with [`ClientBuilder::reqwest_client`] or on WebAssembly.
+    pub fn tcp_keepalive(mut self, tcp_keepalive: Duration) -> Self {
+        self.tcp_keepalive = Some(tcp_keepalive);
+        self
+    }
+
+    /// Adds a proxy to send requests through, e.g. to reach the API from
+    /// behind a corporate proxy.
+    ///
+    /// Can be called more than once to add several proxies, which are tried in
+    /// the order they were added. Like [`ClientBuilder::connect_timeout`],
+    /// this has no effect if a client was provided with
+    /// [`ClientBuilder::reqwest_client`] or on WebAssembly.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Enables retrying requests that fail with a server error (5xx) or a
+    /// network error, up to `max_retries` times.
+    ///
+    /// Building the client fails if `max_retries` or `base_delay` is zero.
+    ///
+    /// The delay before each retry starts at roughly `base_delay` and grows
+    /// exponentially, unless the server sends a `Retry-After` header, which is
+    /// respected instead. Client errors (4xx), including an invalid token, are
+    /// never retried. Retrying stops early once the total time spent waiting
+    /// would exceed the limit set with [`ClientBuilder::max_retry_wait`].
+    pub fn retry(mut self, max_retries: u32, base_delay: Duration) -> Self {
+        self.retry = Some((max_retries, base_delay));
+        self
+    }
+
+    /// Sets the maximum total time spent waiting between retries of a single
+    /// request.
+    ///
+    /// Defaults to 60 seconds. Has no effect unless retrying has been enabled
+    /// with [`ClientBuilder::retry`].
+    pub fn max_retry_wait(mut self, max_retry_wait: Duration) -> Self {
+        self.max_retry_wait = max_retry_wait;
+        self
+    }
+
+    /// Limits requests to `requests_per_second`, waiting before sending any
+    /// that would exceed it, to avoid being rate limited by the API.
+    ///
+    /// Up to a second's worth of requests can be sent at once before waiting
+    /// starts. Clones of the client share the limit, so concurrent requests
+    /// made through any of them count towards it, and so do retries. Not
+    /// available on WebAssembly. Building the client fails if
+    /// `requests_per_second` is zero.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.rate_limit = Some(requests_per_second);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    ///
+    /// Defaults to `buy-me-a-coffee-rs/` followed by this crate's version. To
+    /// identify an application while still identifying this crate, include
+    /// both, e.g. `my-app/1.0 buy-me-a-coffee-rs/0.3.1`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Sets how many items to request per page when listing members,
+    /// onetime-supporters, or extra purchases.
+    ///
+    /// By default, no size is requested and the API uses its own, reported in
+    /// [`Page::per_page`]. Bigger pages mean fewer requests when walking through
+    /// every page. The API may cap the size at a maximum of its own, so check
+    /// [`Page::per_page`] for the size actually used. Building the client fails
+    /// if `per_page` is zero.
+    ///
+    /// [`Page::per_page`]: crate::Page::per_page
+    pub fn per_page(mut self, per_page: u16) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Sets whether HTML responses are treated as [`Error::Unauthorized`].
+    ///
+    /// Defaults to `true`, since the API redirects to its login page when the
+    /// token is rejected. Other HTML pages, like a captcha or a maintenance
+    /// page, are then mistaken for it. Disabling this makes them fail with
+    /// [`Error::Deserialize`] instead, which holds the body for inspection.
+    pub fn treat_html_as_unauthorized(mut self, treat_html_as_unauthorized: bool) -> Self {
+        self.treat_html_as_unauthorized = treat_html_as_unauthorized;
+        self
+    }
+
+    /// Sets whether JSON responses that don't have the shape expected fail
+    /// with [`Error::Untyped`], holding the whole response, instead of
+    /// [`Error::Deserialize`].
+    ///
+    /// Defaults to `false`. Enabling this makes it possible to recover the
+    /// data of a response from the error if the API changes in a way this
+    /// crate doesn't handle yet. Responses that aren't JSON at all still fail
+    /// with [`Error::Deserialize`].
+    pub fn untyped_fallback(mut self, untyped_fallback: bool) -> Self {
+        self.untyped_fallback = untyped_fallback;
+        self
+    }
+
+    /// Enables caching successful responses in memory for `ttl`.
+    ///
+    /// While a response is cached, requesting the same endpoint with the same
+    /// parameters returns a clone of it without making a request. The cache is
+    /// shared between clones of the client and can be emptied with
+    /// [`Client::clear_cache`]. The blocking client doesn't cache.
+    #[cfg(feature = "cache")]
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Creates a [`blocking::Client`] with the configuration provided.
+    ///
+    /// Any client provided with [`ClientBuilder::reqwest_client`] is ignored,
+    /// since it isn't blocking. Fails like [`ClientBuilder::build`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an asynchronous runtime, like
+    /// [`reqwest::blocking::Client::new`].
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub fn build_blocking(self) -> Result<blocking::Client> {
+        self.validate()?;
+        self.build_blocking_unvalidated()
+    }
+
+    /// Like [`ClientBuilder::build_blocking`], but without checking the
+    /// configuration, for [`blocking::Client::new`].
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub(crate) fn build_blocking_unvalidated(self) -> Result<blocking::Client> {
+        let retry = self.retry_policy();
+        let rate_limiter = self.rate_limiter();
+        let mut builder = reqwest::blocking::Client::builder();
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(pool_idle_timeout);
+        }
+
+        if let Some(tcp_keepalive) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(tcp_keepalive);
+        }
+
+        for proxy in self.proxies {
+            builder = builder.proxy(proxy);
+        }
+
+        Ok(blocking::Client {
+            client: builder.build()?,
+            token: self.token,
+            base_url: self.base_url,
+            timeout: self.timeout,
+            retry,
+            user_agent: self.user_agent,
+            per_page: self.per_page,
+            treat_html_as_unauthorized: self.treat_html_as_unauthorized,
+            untyped_fallback: self.untyped_fallback,
+            rate_limiter,
+            last_pages: Default::default(),
+        })
+    }
+
+    /// Checks that the configuration provided can work, so that building fails
+    /// rather than every request.
+    fn validate(&self) -> Result<()> {
+        if self.token.trim().is_empty() {
+            return Err(Error::InvalidToken);
+        }
+
+        if !Url::parse(&self.base_url).is_ok_and(|url| matches!(url.scheme(), "http" | "https")) {
+            return Err(Error::InvalidConfig(format!(
+                "base URL `{}` isn't an HTTP or HTTPS URL",
+                self.base_url,
+            )));
+        }
+
+        if let Some((max_retries, base_delay)) = self.retry {
+            if max_retries == 0 {
+                return Err(Error::InvalidConfig(
+                    "retrying enabled with zero retries".to_string(),
+                ));
+            }
+
+            if base_delay.is_zero() {
+                return Err(Error::InvalidConfig(
+                    "retrying enabled without a delay".to_string(),
+                ));
+            }
+        }
+
+        if self.rate_limit == Some(0) {
+            return Err(Error::InvalidConfig(
+                "rate limit of zero requests per second".to_string(),
+            ));
+        }
+
+        if self.per_page == Some(0) {
+            return Err(Error::InvalidConfig("page size of zero".to_string()));
+        }
+
+        if HeaderValue::from_str(&self.user_agent).is_err() {
+            return Err(Error::InvalidConfig(format!(
+                "user agent `{}` isn't a valid header value",
+                self.user_agent,
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn retry_policy(&self) -> Option<Retry> {
+        self.retry.map(|(max_retries, base_delay)| Retry {
+            max_retries,
+            base_delay,
+            max_wait: self.max_retry_wait,
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn rate_limiter(&self) -> Option<Arc<RateLimiter>> {
+        self.rate_limit
+            .map(|requests_per_second| Arc::new(RateLimiter::new(requests_per_second)))
+    }
+
+    /// Creates a [`Client`] with the configuration provided.
+    ///
+    /// Returns [`Error::InvalidToken`] if the token is empty or only
+    /// whitespace, and [`Error::InvalidConfig`] if any other option set can't
+    /// work, e.g. a base URL that isn't an HTTP or HTTPS URL. Returns
+    /// [`Error::Reqwest`] if no client was provided and a TLS backend cannot be
+    /// initialized, like [`reqwest::Client::new`].
+    pub fn build(self) -> Result<Client> {
+        self.validate()?;
+        self.build_unvalidated()
+    }
+
+    /// Like [`ClientBuilder::build`], but without checking the configuration,
+    /// for [`Client::new`], which accepted any token before building could
+    /// fail.
+    pub(crate) fn build_unvalidated(self) -> Result<Client> {
+        let retry = self.retry_policy();
+        #[cfg(not(target_arch = "wasm32"))]
+        let rate_limiter = self.rate_limiter();
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                #[allow(unused_mut)]
+                let mut builder = reqwest::Client::builder();
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    if let Some(connect_timeout) = self.connect_timeout {
+                        builder = builder.connect_timeout(connect_timeout);
+                    }
+
+                    if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+                        builder = builder.pool_idle_timeout(pool_idle_timeout);
+                    }
+
+                    if let Some(tcp_keepalive) = self.tcp_keepalive {
+                        builder = builder.tcp_keepalive(tcp_keepalive);
+                    }
+
+                    for proxy in self.proxies {
+                        builder = builder.proxy(proxy);
+                    }
+                }
+
+                builder.build()?
+            }
+        };
+
+        let transport = self.transport.unwrap_or_else(|| Arc::new(client.clone()));
+
+        Ok(Client {
+            inner: Arc::new(Inner {
+                client,
+                transport,
+                token: self.token,
+                base_url: self.base_url,
+                timeout: self.timeout,
+                retry,
+                user_agent: self.user_agent,
+                per_page: self.per_page,
+                treat_html_as_unauthorized: self.treat_html_as_unauthorized,
+                untyped_fallback: self.untyped_fallback,
+                #[cfg(not(target_arch = "wasm32"))]
+                rate_limiter,
+                #[cfg(feature = "cache")]
+                cache: self.cache_ttl.map(Cache::new),
+                validators: Default::default(),
+                last_pages: Default::default(),
+            }),
+            retries: None,
+        })
+    }
+}
@@ -0,0 +1,71 @@
+//! An in-memory cache of responses, shared between clones of a
+//! [`Client`](crate::Client).
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+type Entry = (Instant, Arc<dyn Any + Send + Sync>);
+
+pub(crate) struct Cache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(String, TypeId), Entry>>,
+}
+
+impl Debug for Cache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cache")
+            .field("ttl", &self.ttl)
+            .field("len", &self.entries().len())
+            .finish()
+    }
+}
+
+impl Cache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Locks the entries, even if another thread panicked while holding them,
+    /// since every change to them is a single call that can't leave them half
+    /// done.
+    fn entries(&self) -> MutexGuard<'_, HashMap<(String, TypeId), Entry>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns a clone of the value cached for `url`, unless there is none or
+    /// it has expired.
+    pub(crate) fn get<T: Clone + Send + Sync + 'static>(&self, url: &str) -> Option<T> {
+        let mut entries = self.entries();
+        let key = (url.to_string(), TypeId::of::<T>());
+        let (cached_at, value) = entries.get(&key)?;
+
+        if cached_at.elapsed() >= self.ttl {
+            entries.remove(&key);
+
+            return None;
+        }
+
+        value.downcast_ref::<T>().cloned()
+    }
+
+    /// Caches `value` for `url`, removing every entry that has expired so
+    /// that entries which are never requested again don't pile up.
+    pub(crate) fn insert<T: Clone + Send + Sync + 'static>(&self, url: String, value: T) {
+        let mut entries = self.entries();
+
+        entries.retain(|_, (cached_at, _)| cached_at.elapsed() < self.ttl);
+        entries.insert((url, TypeId::of::<T>()), (Instant::now(), Arc::new(value)));
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries().clear();
+    }
+}
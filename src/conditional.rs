@@ -0,0 +1,57 @@
+//! The `ETag` and `Last-Modified` validators of responses, shared between
+//! clones of a [`Client`](crate::Client) so that conditional requests can be
+//! made.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    Request,
+};
+
+#[derive(Debug, Clone)]
+struct Entry {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Validators {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl Validators {
+    /// Adds `If-None-Match` and `If-Modified-Since` headers to `request` if
+    /// validators were stored for its URL.
+    pub(crate) fn apply(&self, request: &mut Request) {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        let Some(entry) = entries.get(request.url().as_str()) else {
+            return;
+        };
+        let headers = request.headers_mut();
+
+        if let Some(etag) = &entry.etag {
+            headers.insert(IF_NONE_MATCH, etag.clone());
+        }
+
+        if let Some(last_modified) = &entry.last_modified {
+            headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+    }
+
+    /// Stores the validators of a response to `url`, or forgets the previous
+    /// ones if it has none.
+    pub(crate) fn update(&self, url: String, headers: &HeaderMap) {
+        let entry = Entry {
+            etag: headers.get(ETAG).cloned(),
+            last_modified: headers.get(LAST_MODIFIED).cloned(),
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+
+        if entry.etag.is_none() && entry.last_modified.is_none() {
+            entries.remove(&url);
+        } else {
+            entries.insert(url, entry);
+        }
+    }
+}
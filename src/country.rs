@@ -0,0 +1,83 @@
+//! Two-letter country codes as defined by ISO 3166-1 alpha-2.
+
+use std::{
+    convert::Infallible,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! countries {
+    ($($code:ident)*) => {
+        /// A country, identified by its ISO 3166-1 alpha-2 code.
+        ///
+        /// Codes are matched case-insensitively. Anything that isn't an
+        /// assigned code ends up in [`Country::Unknown`] as it was sent, so a
+        /// malformed country never fails deserialization.
+        #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+        #[serde(from = "String", into = "String")]
+        pub enum Country {
+            $(
+                #[doc = concat!("`", stringify!($code), "`")]
+                $code,
+            )*
+            /// A code that isn't an assigned ISO 3166-1 alpha-2 code.
+            Unknown(String),
+        }
+
+        impl Country {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Country::$code => stringify!($code),)*
+                    Country::Unknown(unknown) => unknown,
+                }
+            }
+        }
+
+        impl From<String> for Country {
+            fn from(value: String) -> Self {
+                match value.trim().to_ascii_uppercase().as_str() {
+                    $(stringify!($code) => Country::$code,)*
+                    _ => Country::Unknown(value),
+                }
+            }
+        }
+    };
+}
+
+countries! {
+    AD AE AF AG AI AL AM AO AQ AR AS AT AU AW AX AZ BA BB BD BE BF BG BH BI BJ
+    BL BM BN BO BQ BR BS BT BV BW BY BZ CA CC CD CF CG CH CI CK CL CM CN CO CR
+    CU CV CW CX CY CZ DE DJ DK DM DO DZ EC EE EG EH ER ES ET FI FJ FK FM FO FR
+    GA GB GD GE GF GG GH GI GL GM GN GP GQ GR GS GT GU GW GY HK HM HN HR HT HU
+    ID IE IL IM IN IO IQ IR IS IT JE JM JO JP KE KG KH KI KM KN KP KR KW KY KZ
+    LA LB LC LI LK LR LS LT LU LV LY MA MC MD ME MF MG MH MK ML MM MN MO MP MQ
+    MR MS MT MU MV MW MX MY MZ NA NC NE NF NG NI NL NO NP NR NU NZ OM PA PE PF
+    PG PH PK PL PM PN PR PS PT PW PY QA RE RO RS RU RW SA SB SC SD SE SG SH SI
+    SJ SK SL SM SN SO SR SS ST SV SX SY SZ TC TD TF TG TH TJ TK TL TM TN TO TR
+    TT TV TW TZ UA UG UM US UY UZ VA VC VE VG VI VN VU WF WS YE YT ZA ZM ZW
+}
+
+impl From<Country> for String {
+    fn from(value: Country) -> Self {
+        match value {
+            Country::Unknown(unknown) => unknown,
+            _ => value.as_str().to_string(),
+        }
+    }
+}
+
+impl FromStr for Country {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.to_string().into())
+    }
+}
+
+impl Display for Country {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
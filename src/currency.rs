@@ -0,0 +1,130 @@
+//! Three-letter currency codes as defined by ISO 4217.
+
+use std::{
+    convert::Infallible,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! currencies {
+    ($($code:ident $minor_units:literal,)*) => {
+        /// A currency, identified by its ISO 4217 code.
+        ///
+        /// Codes are matched case-insensitively. Anything that isn't an active
+        /// code ends up in [`Currency::Unknown`] as it was sent, so an
+        /// unexpected currency never fails deserialization.
+        #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+        #[serde(from = "String", into = "String")]
+        pub enum Currency {
+            $(
+                #[doc = concat!("`", stringify!($code), "`")]
+                $code,
+            )*
+            /// A code that isn't an active ISO 4217 code.
+            Unknown(String),
+        }
+
+        impl Currency {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Currency::$code => stringify!($code),)*
+                    Currency::Unknown(unknown) => unknown,
+                }
+            }
+
+            /// Returns how many digits amounts in this currency have after the
+            /// decimal point, e.g. `2` for cents, or [`None`] if unknown.
+            pub fn minor_units(&self) -> Option<u32> {
+                match self {
+                    $(Currency::$code => Some($minor_units),)*
+                    Currency::Unknown(_) => None,
+                }
+            }
+        }
+
+        impl From<String> for Currency {
+            fn from(value: String) -> Self {
+                match value.trim().to_ascii_uppercase().as_str() {
+                    $(stringify!($code) => Currency::$code,)*
+                    _ => Currency::Unknown(value),
+                }
+            }
+        }
+    };
+}
+
+currencies! {
+    AED 2, AFN 2, ALL 2, AMD 2, AOA 2, ARS 2, AUD 2, AWG 2, AZN 2, BAM 2,
+    BBD 2, BDT 2, BGN 2, BHD 3, BIF 0, BMD 2, BND 2, BOB 2, BOV 2, BRL 2,
+    BSD 2, BTN 2, BWP 2, BYN 2, BZD 2, CAD 2, CDF 2, CHE 2, CHF 2, CHW 2,
+    CLF 4, CLP 0, CNY 2, COP 2, COU 2, CRC 2, CUP 2, CVE 2, CZK 2, DJF 0,
+    DKK 2, DOP 2, DZD 2, EGP 2, ERN 2, ETB 2, EUR 2, FJD 2, FKP 2, GBP 2,
+    GEL 2, GHS 2, GIP 2, GMD 2, GNF 0, GTQ 2, GYD 2, HKD 2, HNL 2, HTG 2,
+    HUF 2, IDR 2, ILS 2, INR 2, IQD 3, IRR 2, ISK 0, JMD 2, JOD 3, JPY 0,
+    KES 2, KGS 2, KHR 2, KMF 0, KPW 2, KRW 0, KWD 3, KYD 2, KZT 2, LAK 2,
+    LBP 2, LKR 2, LRD 2, LSL 2, LYD 3, MAD 2, MDL 2, MGA 2, MKD 2, MMK 2,
+    MNT 2, MOP 2, MRU 2, MUR 2, MVR 2, MWK 2, MXN 2, MXV 2, MYR 2, MZN 2,
+    NAD 2, NGN 2, NIO 2, NOK 2, NPR 2, NZD 2, OMR 3, PAB 2, PEN 2, PGK 2,
+    PHP 2, PKR 2, PLN 2, PYG 0, QAR 2, RON 2, RSD 2, RUB 2, RWF 0, SAR 2,
+    SBD 2, SCR 2, SDG 2, SEK 2, SGD 2, SHP 2, SLE 2, SOS 2, SRD 2, SSP 2,
+    STN 2, SVC 2, SYP 2, SZL 2, THB 2, TJS 2, TMT 2, TND 3, TOP 2, TRY 2,
+    TTD 2, TWD 2, TZS 2, UAH 2, UGX 0, USD 2, USN 2, UYI 0, UYU 2, UYW 4,
+    UZS 2, VED 2, VES 2, VND 0, VUV 0, WST 2, XAF 0, XCD 2, XCG 2, XOF 0,
+    XPF 0, YER 2, ZAR 2, ZMW 2, ZWG 2,
+}
+
+impl From<Currency> for String {
+    fn from(value: Currency) -> Self {
+        match value {
+            Currency::Unknown(unknown) => unknown,
+            _ => value.as_str().to_string(),
+        }
+    }
+}
+
+impl FromStr for Currency {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.to_string().into())
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An amount of money in a particular currency.
+///
+/// Displays the amount rounded (half away from zero) to the currency's [minor
+/// units](Currency::minor_units) followed by its code, e.g. `5.00 USD` or
+/// `500 JPY`.
+#[cfg(feature = "rust_decimal")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Money {
+    pub amount: rust_decimal::Decimal,
+    pub currency: Currency,
+}
+
+#[cfg(feature = "rust_decimal")]
+impl Display for Money {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.currency.minor_units() {
+            Some(minor_units) => write!(
+                f,
+                "{:.*} {}",
+                minor_units as usize,
+                self.amount.round_dp_with_strategy(
+                    minor_units,
+                    rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+                ),
+                self.currency,
+            ),
+            None => write!(f, "{} {}", self.amount, self.currency),
+        }
+    }
+}
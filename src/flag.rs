@@ -0,0 +1,36 @@
+//! (De)serialization of the booleans sent by the API and its webhooks
+//! inconsistently, as `true`, `1`, or `"1"` depending on the endpoint.
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+pub(crate) fn serialize<S: Serializer>(flag: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u8(u8::from(*flag))
+}
+
+/// Parses a boolean, or `0` or `1`, as either itself or a string. An empty
+/// string is `false`.
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Flag {
+        Bool(bool),
+        Number(u64),
+        String(String),
+    }
+
+    match Flag::deserialize(deserializer)? {
+        Flag::Bool(flag) => Ok(flag),
+        Flag::Number(0) => Ok(false),
+        Flag::Number(1) => Ok(true),
+        Flag::String(flag) => match flag.trim() {
+            "0" | "false" | "" => Ok(false),
+            "1" | "true" => Ok(true),
+            _ => Err(D::Error::custom(format!(
+                "expected a boolean, 0, or 1, got {flag:?}"
+            ))),
+        },
+        Flag::Number(flag) => Err(D::Error::custom(format!(
+            "expected a boolean, 0, or 1, got {flag}"
+        ))),
+    }
+}
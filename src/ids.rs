@@ -0,0 +1,54 @@
+//! Opaque identifiers that are sent by the API as strings.
+
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! ids {
+    ($($(#[$attr:meta])* $name:ident)*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+            #[serde(transparent)]
+            pub struct $name(String);
+
+            impl $name {
+                pub fn as_str(&self) -> &str {
+                    &self.0
+                }
+            }
+
+            impl From<String> for $name {
+                fn from(value: String) -> Self {
+                    Self(value)
+                }
+            }
+
+            impl From<$name> for String {
+                fn from(value: $name) -> Self {
+                    value.0
+                }
+            }
+
+            impl AsRef<str> for $name {
+                fn as_ref(&self) -> &str {
+                    &self.0
+                }
+            }
+
+            impl Display for $name {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    f.write_str(&self.0)
+                }
+            }
+        )*
+    };
+}
+
+ids! {
+    /// The ID of the payment processor's transaction behind a membership or
+    /// support, e.g. `pi_3Mc51bJEtINljGAa0zVykgUE` for Stripe.
+    TransactionId
+    /// The ID of the transfer that paid a support out to the creator.
+    TransferId
+}
@@ -0,0 +1,77 @@
+//! URLs of images, like the ones of extras.
+
+use std::{
+    convert::Infallible,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The URL of an image.
+///
+/// Anything that can't be parsed as a URL ends up in [`Image::Invalid`] as it
+/// was sent, so a malformed URL never fails deserialization.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum Image {
+    Url(Url),
+    /// A string that isn't a valid URL.
+    Invalid(String),
+}
+
+impl Image {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Image::Url(url) => url.as_str(),
+            Image::Invalid(invalid) => invalid,
+        }
+    }
+
+    /// Returns the parsed URL, or [`None`] if it isn't valid.
+    pub fn url(&self) -> Option<&Url> {
+        match self {
+            Image::Url(url) => Some(url),
+            Image::Invalid(_) => None,
+        }
+    }
+}
+
+impl From<String> for Image {
+    fn from(value: String) -> Self {
+        match Url::parse(value.trim()) {
+            Ok(url) => Image::Url(url),
+            Err(_) => Image::Invalid(value),
+        }
+    }
+}
+
+impl From<Image> for String {
+    fn from(value: Image) -> Self {
+        match value {
+            Image::Url(url) => url.into(),
+            Image::Invalid(invalid) => invalid,
+        }
+    }
+}
+
+impl From<Url> for Image {
+    fn from(value: Url) -> Self {
+        Image::Url(value)
+    }
+}
+
+impl FromStr for Image {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.to_string().into())
+    }
+}
+
+impl Display for Image {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -8,204 +8,2111 @@
 //!
 //! # const EARLY_ACCESS_ID: u32 = 0;
 //! #
-//! async fn has_early_access(email: String) -> bool {
+//! async fn has_early_access(email: &str) -> bool {
 //!     let client = buy_me_a_coffee::Client::new("personal access token here");
-//!     let mut page_num = 1;
 //!
-//!     while let Ok(page) = client.members(MemberStatus::Active, page_num).await {
-//!         for membership in page.data {
-//!             if membership.payer_email != email {
-//!                 continue;
-//!             }
+//!     match client.find_member_by_email(MemberStatus::Active, email).await {
+//!         Ok(Some(membership)) => membership.id == EARLY_ACCESS_ID,
+//!         _ => false,
+//!     }
+//! }
+//! ```
 //!
-//!             if membership.id != EARLY_ACCESS_ID {
-//!                 continue;
-//!             }
+//! # Feature flags
 //!
-//!             return true;
-//!         }
+//! - `async-trait`: enables [`SubscriptionProvider`], a trait for code that is
+//!   generic over where memberships come from.
+//! - `blocking`: enables the [`blocking`] module, a synchronous alternative to
+//!   [`Client`].
+//! - `cache`: enables caching responses in memory for a while, see
+//!   [`ClientBuilder::cache_ttl`].
+//! - `chrono`: parses timestamps into `chrono::DateTime<Utc>` (see
+//!   [`Timestamp`]).
+//! - `clap`: implements `clap::ValueEnum` for [`MemberStatus`], so it can be
+//!   used as the type of a command-line argument.
+//! - `country`: parses countries into a [`Country`] enum of ISO 3166-1
+//!   alpha-2 codes.
+//! - `tracing`: emits a `tracing` span for every request, recording its
+//!   endpoint, page, and response status, and an error event if it fails. The
+//!   token is never recorded.
+//! - `currency`: parses currencies into a [`Currency`] enum of ISO 4217
+//!   codes. Together with `rust_decimal`, also enables `Money` for displaying
+//!   amounts.
+//! - `log`: logs every request's URL and response status at the debug level
+//!   with the `log` crate. The token is always masked.
+//! - `ring`: enables `webhook::verify_signature`, using `ring` for HMAC.
+//! - `rust_decimal`: parses prices into `rust_decimal::Decimal` (see
+//!   [`Price`] and [`CoffeePrice`]).
+//! - `url`: parses image URLs into `url::Url` (see [`Image`]), and adds
+//!   `referer_url` and `referer_host` to [`Membership`] and [`Support`].
 //!
-//!         page_num += 1;
-//!     }
+//! # WebAssembly
 //!
-//!     false
-//! }
-//! ```
+//! This crate compiles for `wasm32-unknown-unknown`, using the browser's
+//! `fetch` through reqwest. The `blocking` and `cache` features aren't
+//! available there, neither is [`ClientBuilder::rate_limit`], and
+//! [`ClientBuilder::connect_timeout`] has no effect.
+//!
+//! Keep in mind that the API isn't meant to be called from browsers: it may
+//! not send the CORS headers a browser requires to let a page read its
+//! responses, and any token shipped to a browser can be read by whoever
+//! visits the page. Call it from a server instead, e.g. a Leptos server
+//! function, and pass only what a page needs on to it.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    convert::Infallible,
+    fmt::{self, Debug, Display, Formatter},
+    future::{ready, Future},
+    path::Path,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    task::Poll,
+    time::Duration,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+mod builder;
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(all(feature = "cache", target_arch = "wasm32"))]
+compile_error!("the `cache` feature isn't supported on WebAssembly");
+mod conditional;
+#[cfg(feature = "country")]
+mod country;
+#[cfg(feature = "currency")]
+mod currency;
+mod flag;
+mod ids;
+#[cfg(feature = "url")]
+mod image;
+mod optional;
+mod pages;
+#[cfg(feature = "rust_decimal")]
+mod price;
+#[cfg(feature = "async-trait")]
+mod provider;
+#[cfg(not(target_arch = "wasm32"))]
+mod rate_limit;
+#[cfg(feature = "url")]
+mod referer;
+mod retry;
+#[cfg(feature = "chrono")]
+mod timestamp;
+mod transport;
+pub mod webhook;
+
+use retry::{Backoff, Retry};
+
+use futures::{stream, stream::FusedStream, Stream, StreamExt, TryStreamExt};
+use reqwest::{
+    header::{HeaderMap, CONTENT_TYPE, USER_AGENT},
+    Request, RequestBuilder, Response, StatusCode, Url,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+pub use builder::ClientBuilder;
+pub use ids::{TransactionId, TransferId};
+pub use pages::{ExtraPages, MemberPages, SupporterPages};
+#[cfg(feature = "async-trait")]
+pub use provider::SubscriptionProvider;
+pub use retry::Attempts;
+#[cfg(not(target_arch = "wasm32"))]
+pub use transport::ReplayTransport;
+pub use transport::{HttpTransport, TransportFuture};
+
+const PREFIX: &str = "https://developers.buymeacoffee.com/api";
+const USER_AGENT_VALUE: &str = concat!("buy-me-a-coffee-rs/", env!("CARGO_PKG_VERSION"));
+/// The environment variable read by [`Client::from_env`].
+const TOKEN_ENV_VAR: &str = "BUYMEACOFFEE_TOKEN";
+/// The key [`Client::supporters_by_country`] groups supporters without a
+/// country under.
+const UNKNOWN_COUNTRY: &str = "unknown";
+/// What personal information is replaced with by `redacted_debug` methods.
+const REDACTED: &str = "[redacted]";
+/// How many requests [`Client::memberships`] makes at once.
+const MEMBERSHIPS_CONCURRENCY: usize = 4;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A point in time as returned by the API.
+///
+/// With the `chrono` feature enabled, this is a `chrono::DateTime<Utc>`.
+/// Otherwise, it's the raw string sent by the API, e.g. `2024-01-02 15:04:05`.
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+/// A point in time as returned by the API.
+///
+/// With the `chrono` feature enabled, this is a `chrono::DateTime<Utc>`.
+/// Otherwise, it's the raw string sent by the API, e.g. `2024-01-02 15:04:05`.
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = String;
+
+#[cfg(feature = "country")]
+pub use country::Country;
+/// A country as returned by the API.
+///
+/// With the `country` feature enabled, this is an enum of ISO 3166-1 alpha-2
+/// codes. Otherwise, it's the raw string sent by the API, e.g. `US`.
+#[cfg(not(feature = "country"))]
+pub type Country = String;
+
+#[cfg(feature = "currency")]
+pub use currency::Currency;
+#[cfg(all(feature = "currency", feature = "rust_decimal"))]
+pub use currency::Money;
+/// A currency as returned by the API.
+///
+/// With the `currency` feature enabled, this is an enum of ISO 4217 codes.
+/// Otherwise, it's the raw string sent by the API, e.g. `USD`.
+#[cfg(not(feature = "currency"))]
+pub type Currency = String;
+
+#[cfg(feature = "url")]
+pub use image::Image;
+/// The URL of an image as returned by the API.
+///
+/// With the `url` feature enabled, this is an enum holding a `url::Url`, or
+/// the raw string if it isn't a valid URL. Otherwise, it's the raw string sent
+/// by the API, e.g. `https://cdn.buymeacoffee.com/uploads/rewards/1.png`.
+#[cfg(not(feature = "url"))]
+pub type Image = String;
+
+/// An amount of money as returned by the API.
+///
+/// With the `rust_decimal` feature enabled, this is a `rust_decimal::Decimal`.
+/// Otherwise, it's the raw string sent by the API, e.g. `5.0000`.
+#[cfg(feature = "rust_decimal")]
+pub type Price = rust_decimal::Decimal;
+/// An amount of money as returned by the API.
+///
+/// With the `rust_decimal` feature enabled, this is a `rust_decimal::Decimal`.
+/// Otherwise, it's the raw string sent by the API, e.g. `5.0000`.
+#[cfg(not(feature = "rust_decimal"))]
+pub type Price = String;
+
+#[cfg(feature = "rust_decimal")]
+pub use price::CoffeePrice;
+/// The price of a single coffee as returned by the API.
+///
+/// With the `rust_decimal` feature enabled, this is a struct holding the raw
+/// string and, if it's a valid number, a `rust_decimal::Decimal`. Otherwise,
+/// it's the raw string sent by the API, e.g. `5.0000`.
+#[cfg(not(feature = "rust_decimal"))]
+pub type CoffeePrice = String;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    /// The response had a shape this crate didn't expect, which may mean the
+    /// API has changed.
+    ///
+    /// `body` is the response as it was received, truncated to 2 KiB.
+    #[error("unexpected response ({source}): {body}")]
+    Deserialize {
+        source: serde_json::Error,
+        body: String,
+    },
+    /// The API responded with a client error (4xx) without describing it in
+    /// the body. If it did, [`Error::Server`] is returned instead.
+    #[error("{0}")]
+    Client(StatusCode),
+    /// The personal access token was rejected.
+    ///
+    /// `redirected` is whether the API redirected to its login page, as it
+    /// usually does, rather than responding with 401 Unauthorized. Any HTML
+    /// response is assumed to be the login page, unless disabled with
+    /// [`ClientBuilder::treat_html_as_unauthorized`].
+    #[error("unauthorized{}", if *.redirected { " (redirected to login page)" } else { "" })]
+    Unauthorized { redirected: bool },
+    #[error(transparent)]
+    Server(#[from] ServerError),
+    /// The API responded with 429 Too Many Requests.
+    ///
+    /// `retry_after` is how long the API asked to wait before trying again,
+    /// if it said so.
+    #[error("rate limited{}", if let Some(retry_after) = .retry_after { format!(", retry after {retry_after:?}") } else { "".to_string() })]
+    RateLimited { retry_after: Option<Duration> },
+    /// The personal access token is empty or only whitespace.
+    #[error("invalid personal access token")]
+    InvalidToken,
+    /// A page past the last one was requested.
+    ///
+    /// The API responds to those as if there was nothing to list, so this is
+    /// only returned if an earlier response said which page is the last.
+    #[error("page {requested} is past the last page, {last_page}")]
+    PageOutOfRange { requested: u16, last_page: u16 },
+    /// The configuration given to a [`ClientBuilder`] can't work, for the
+    /// reason contained.
+    #[error("invalid client configuration: {0}")]
+    InvalidConfig(String),
+    /// An operation given a deadline, like [`Client::all_supporters_until`],
+    /// didn't finish before it.
+    #[error("deadline exceeded")]
+    DeadlineExceeded,
+    /// The API responded successfully, with the status contained, but without
+    /// a body.
+    ///
+    /// Listings treat this as if there was nothing to list, so this is only
+    /// returned where something was expected, like [`Client::support`].
+    #[error("empty response ({0})")]
+    EmptyResponse(StatusCode),
+    /// The response had a shape this crate didn't expect, but was still JSON,
+    /// which is contained. Only returned instead of [`Error::Deserialize`] if
+    /// enabled with [`ClientBuilder::untyped_fallback`].
+    #[error("unexpected response: {0}")]
+    Untyped(serde_json::Value),
+    /// A file couldn't be read, like the token file given to
+    /// [`Client::from_file`].
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Error, Deserialize)]
+#[error("{}{reason}", if let Some(error_code) = .error_code { format!("{error_code} ") } else { "".to_string() })]
+pub struct ServerError {
+    pub error_code: Option<u16>,
+    #[serde(alias = "error")]
+    pub reason: String,
+    /// The HTTP status the error was sent with.
+    ///
+    /// The API sends some errors with 200 OK and others with a client error
+    /// (4xx) status.
+    #[serde(skip)]
+    pub status: StatusCode,
+}
+
+impl ServerError {
+    /// Classifies this error by its [`ServerError::reason`].
+    pub fn kind(&self) -> ServerErrorKind {
+        match self.reason.as_str() {
+            "No subscriptions" => ServerErrorKind::NoSubscriptions,
+            "No supporters" => ServerErrorKind::NoSupporters,
+            "No extra purchases" => ServerErrorKind::NoExtraPurchases,
+            _ => ServerErrorKind::Other,
+        }
+    }
+}
+
+/// What a [`ServerError`] means, as returned by [`ServerError::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerErrorKind {
+    /// There are no members to list.
+    NoSubscriptions,
+    /// There are no onetime-supporters to list.
+    NoSupporters,
+    /// There are no extra purchases to list.
+    NoExtraPurchases,
+    /// Any other error.
+    Other,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UntaggedResult<T> {
+    Err(ServerError),
+    Ok(T),
+}
+
+/// How much of a response body is kept in [`Error::Deserialize`].
+const MAX_ERROR_BODY_LEN: usize = 2048;
+
+/// Checks a response's `status` and `headers` for errors, then parses its
+/// `body`.
+///
+/// Client errors (4xx) become [`Error::Server`] if the body describes them,
+/// or [`Error::Client`] otherwise. See [`check_response`] for
+/// `treat_html_as_unauthorized`, and [`parse_body`] for `untyped_fallback`.
+fn parse_response<T: DeserializeOwned>(
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: &[u8],
+    treat_html_as_unauthorized: bool,
+    untyped_fallback: bool,
+) -> Result<T> {
+    match check_response(status, headers, treat_html_as_unauthorized) {
+        Err(Error::Client(status)) => match serde_json::from_slice::<ServerError>(body) {
+            Ok(err) => Err(Error::Server(ServerError { status, ..err })),
+            Err(_) => Err(Error::Client(status)),
+        },
+        result => result.and_then(|()| parse_body(status, body, untyped_fallback)),
+    }
+}
+
+/// Parses the `body` of a response sent with `status`, keeping a truncated
+/// copy of it in the error if it isn't shaped as expected, or all of it as
+/// [`Error::Untyped`] if `untyped_fallback` is set and it's still JSON.
+fn parse_body<T: DeserializeOwned>(
+    status: StatusCode,
+    body: &[u8],
+    untyped_fallback: bool,
+) -> Result<T> {
+    if body.trim_ascii().is_empty() {
+        return Err(Error::EmptyResponse(status));
+    }
+
+    match serde_json::from_slice::<UntaggedResult<T>>(body) {
+        Ok(UntaggedResult::Ok(value)) => Ok(value),
+        Ok(UntaggedResult::Err(err)) => Err(Error::Server(ServerError { status, ..err })),
+        Err(source) if untyped_fallback => match serde_json::from_slice(body) {
+            Ok(value) => Err(Error::Untyped(value)),
+            Err(_) => Err(deserialize_error(source, body)),
+        },
+        Err(source) => Err(deserialize_error(source, body)),
+    }
+}
+
+/// Creates an [`Error::Deserialize`] holding a truncated copy of `body`.
+fn deserialize_error(source: serde_json::Error, body: &[u8]) -> Error {
+    let mut body = String::from_utf8_lossy(body).into_owned();
+
+    if body.len() > MAX_ERROR_BODY_LEN {
+        let mut len = MAX_ERROR_BODY_LEN;
+
+        while !body.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        body.truncate(len);
+        body.push('…');
+    }
+
+    Error::Deserialize { source, body }
+}
+
+/// Runs `receive`, which handles a request to `url` for `endpoint`, within a
+/// span if the `tracing` feature is enabled.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+async fn instrument<T>(
+    endpoint: &str,
+    url: &Url,
+    receive: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+
+        async {
+            let result = receive.await;
+
+            failed(&result);
+            result
+        }
+        .instrument(request_span(endpoint, url))
+        .await
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    receive.await
+}
+
+/// Creates the span that a request to `endpoint` at `url` is made within, for
+/// both the asynchronous and the blocking client.
+#[cfg(feature = "tracing")]
+fn request_span(endpoint: &str, url: &Url) -> tracing::Span {
+    let page = url
+        .query_pairs()
+        .find(|(key, _)| key == "page")
+        .map(|(_, page)| page.into_owned());
+
+    tracing::info_span!(
+        "request",
+        endpoint,
+        page = page.as_deref(),
+        status = tracing::field::Empty,
+    )
+}
+
+/// Records an event in the current span if `result` is an error.
+#[cfg(feature = "tracing")]
+fn failed<T>(result: &Result<T>) {
+    if let Err(err) = result {
+        tracing::error!(error = ?err, "request failed");
+    }
+}
+
+/// Logs that a request to `url` is being sent with `token`, masked, if the
+/// `log` feature is enabled.
+#[cfg_attr(not(feature = "log"), allow(unused_variables))]
+fn sending(url: &Url, token: &str) {
+    #[cfg(feature = "log")]
+    log::debug!("GET {url} with token {}", mask(token));
+}
+
+/// Logs the `status` that a request to `url` was responded to with, and
+/// records it in the current span, if the `log` and `tracing` features are
+/// enabled.
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing")),
+    allow(unused_variables)
+)]
+fn received(url: &Url, status: StatusCode) {
+    #[cfg(feature = "log")]
+    log::debug!("GET {url} responded with {status}");
+
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("status", status.as_u16());
+}
+
+/// Returns the query parameters selecting `page` of a listing, and its size if
+/// one was set.
+fn page_params(page: u16, per_page: Option<u16>) -> Vec<(&'static str, u16)> {
+    let mut params = vec![("page", page)];
+
+    params.extend(per_page.map(|per_page| ("per_page", per_page)));
+    params
+}
+
+/// Adds what every request needs, for both the asynchronous and the blocking
+/// client.
+trait Authorize {
+    /// Authenticates the request with `token` and identifies it with
+    /// `user_agent`, failing it after `timeout` if set.
+    fn authorize(self, token: &str, user_agent: &str, timeout: Option<Duration>) -> Self;
+}
+
+macro_rules! authorize {
+    ($($(#[$attr:meta])* $builder:ty),*) => {
+        $(
+            $(#[$attr])*
+            impl Authorize for $builder {
+                fn authorize(self, token: &str, user_agent: &str, timeout: Option<Duration>) -> Self {
+                    let request = self.bearer_auth(token).header(USER_AGENT, user_agent);
+
+                    match timeout {
+                        Some(timeout) => request.timeout(timeout),
+                        None => request,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+authorize!(
+    RequestBuilder,
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    reqwest::blocking::RequestBuilder
+);
+
+/// Checks a response's `status` and `headers` for errors.
+///
+/// HTML responses are only treated as [`Error::Unauthorized`] if
+/// `treat_html_as_unauthorized` is set, otherwise they fail to parse later.
+fn check_response(
+    status: StatusCode,
+    headers: &HeaderMap,
+    treat_html_as_unauthorized: bool,
+) -> Result<()> {
+    // For some reason, when unauthorized, the API will redirect to the login
+    // page, despite the agent not being a browser. This is annoying, but
+    // consistent enough that we can anticipate it and turn it into an error
+    // that makes sense.
+    if treat_html_as_unauthorized
+        && headers
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| {
+                content_type
+                    .to_str()
+                    .map(|content_type_str| content_type_str.contains("html"))
+                    .ok()
+            })
+            .unwrap_or_default()
+    {
+        return Err(Error::Unauthorized { redirected: true });
+    }
+
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(Error::RateLimited {
+            retry_after: retry::retry_after(headers),
+        });
+    }
+
+    if status == StatusCode::UNAUTHORIZED {
+        return Err(Error::Unauthorized { redirected: false });
+    }
+
+    if status.is_client_error() {
+        return Err(Error::Client(status));
+    }
+
+    Ok(())
+}
+
+/// Replaces every value of `extra_fields` with [`REDACTED`], since fields
+/// this crate doesn't know about might hold anything.
+fn redact_extra_fields(extra_fields: &mut Map<String, Value>) {
+    for value in extra_fields.values_mut() {
+        *value = REDACTED.into();
+    }
+}
+
+/// Replaces every character of a token with an asterisk.
+fn mask(token: &str) -> String {
+    String::from_iter(vec!['*'; token.len()])
+}
+
+/// Makes requests to the API.
+///
+/// Cloning a client is cheap, since every clone shares the same connection
+/// pool, rate limit, and cache. It's meant to be created once and cloned freely
+/// across tasks, rather than created for every request.
+#[derive(Clone)]
+pub struct Client {
+    inner: Arc<Inner>,
+    /// Counts retries for [`Client::with_attempts`].
+    retries: Option<Arc<AtomicU32>>,
+}
+
+/// The state shared by every clone of a [`Client`].
+struct Inner {
+    client: reqwest::Client,
+    transport: Arc<dyn HttpTransport>,
+    token: String,
+    base_url: String,
+    timeout: Option<Duration>,
+    retry: Option<Retry>,
+    user_agent: String,
+    per_page: Option<u16>,
+    treat_html_as_unauthorized: bool,
+    untyped_fallback: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    rate_limiter: Option<Arc<rate_limit::RateLimiter>>,
+    #[cfg(feature = "cache")]
+    cache: Option<cache::Cache>,
+    validators: conditional::Validators,
+    last_pages: pages::LastPages,
+}
+
+impl Debug for Client {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("Client");
+
+        debug_struct
+            .field("client", &self.inner.client)
+            .field("token", &mask(&self.inner.token))
+            .field("base_url", &self.inner.base_url)
+            .field("timeout", &self.inner.timeout)
+            .field("retry", &self.inner.retry)
+            .field("user_agent", &self.inner.user_agent)
+            .field("per_page", &self.inner.per_page)
+            .field(
+                "treat_html_as_unauthorized",
+                &self.inner.treat_html_as_unauthorized,
+            )
+            .field("untyped_fallback", &self.inner.untyped_fallback);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        debug_struct.field("rate_limiter", &self.inner.rate_limiter);
+
+        #[cfg(feature = "cache")]
+        debug_struct.field("cache", &self.inner.cache);
+
+        debug_struct.finish_non_exhaustive()
+    }
+}
+
+impl Client {
+    /// The token isn't checked, so an empty one is only rejected by the API
+    /// once a request is made. Use [`Client::try_new`] to check it up front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a TLS backend cannot be initialized, like
+    /// [`reqwest::Client::new`].
+    pub fn new(token: impl ToString) -> Self {
+        Self::builder(token)
+            .build_unvalidated()
+            .expect("client should be valid")
+    }
+
+    /// Like [`Client::new`], but returns [`Error::InvalidToken`] if `token` is
+    /// empty or only whitespace, rather than waiting for the first request to
+    /// be rejected.
+    pub fn try_new(token: impl ToString) -> Result<Self> {
+        Self::builder(token).build()
+    }
+
+    /// Like [`Client::try_new`], but reads the token from the
+    /// `BUYMEACOFFEE_TOKEN` environment variable.
+    ///
+    /// Returns [`Error::InvalidToken`] if the variable isn't set, isn't valid
+    /// Unicode, or is empty.
+    pub fn from_env() -> Result<Self> {
+        Self::from_env_var(TOKEN_ENV_VAR)
+    }
+
+    /// Like [`Client::from_env`], but reads the token from the environment
+    /// variable `name`.
+    pub fn from_env_var(name: &str) -> Result<Self> {
+        Self::try_new(std::env::var(name).map_err(|_| Error::InvalidToken)?)
+    }
+
+    /// Like [`Client::try_new`], but reads the token from the file at `path`,
+    /// e.g. a secret mounted into a container.
+    ///
+    /// Leading and trailing whitespace, like the newline editors add at the
+    /// end of a file, is trimmed. Returns [`Error::Io`] if the file can't be
+    /// read or isn't valid UTF-8, and [`Error::InvalidToken`] if it holds
+    /// nothing else.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let token = std::fs::read_to_string(path)?;
+
+        Self::try_new(token.trim())
+    }
+
+    /// Returns a [`ClientBuilder`] for configuring a client beyond just its
+    /// token.
+    pub fn builder(token: impl ToString) -> ClientBuilder {
+        ClientBuilder::new(token)
+    }
+
+    /// Adds the query parameters selecting `page` of a listing, and its size
+    /// if one was set with [`ClientBuilder::per_page`].
+    fn page_query(&self, request: RequestBuilder, page: u16) -> RequestBuilder {
+        request.query(&page_params(page, self.inner.per_page))
+    }
+
+    /// Builds an authenticated GET request for `endpoint`.
+    fn request(
+        &self,
+        endpoint: &str,
+        modify_request: impl FnOnce(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Request> {
+        let request = self
+            .inner
+            .client
+            .get(format!("{}{endpoint}", self.inner.base_url))
+            .authorize(
+                &self.inner.token,
+                &self.inner.user_agent,
+                self.inner.timeout,
+            );
+
+        Ok(modify_request(request).build()?)
+    }
+
+    async fn get<T: DeserializeOwned + Clone + Send + Sync + 'static>(
+        &self,
+        endpoint: &str,
+        modify_request: impl FnOnce(RequestBuilder) -> RequestBuilder,
+    ) -> Result<T> {
+        let request = self.request(endpoint, modify_request)?;
+
+        #[cfg(feature = "cache")]
+        let url = request.url().to_string();
+
+        #[cfg(feature = "cache")]
+        if let Some(value) = self.inner.cache.as_ref().and_then(|cache| cache.get(&url)) {
+            return Ok(value);
+        }
+
+        let value: T = self.execute(endpoint, request).await?;
+
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.inner.cache {
+            cache.insert(url, value.clone());
+        }
+
+        Ok(value)
+    }
+
+    /// Like [`Client::receive`], but within a span if the `tracing` feature is
+    /// enabled.
+    async fn execute<T: DeserializeOwned>(&self, endpoint: &str, request: Request) -> Result<T> {
+        let url = request.url().clone();
+
+        instrument(endpoint, &url, self.receive(request)).await
+    }
+
+    /// Like [`Client::get`], but sends the validators of the previous response
+    /// to the same URL, if any, and returns [`None`] if the API responds with
+    /// 304 Not Modified. The cache is never used.
+    async fn get_if_modified<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        modify_request: impl FnOnce(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Option<T>> {
+        let mut request = self.request(endpoint, modify_request)?;
+        let url = request.url().clone();
+
+        self.inner.validators.apply(&mut request);
+
+        instrument(endpoint, &url, async {
+            let response = self.respond(request).await?;
+
+            let status = response.status();
+
+            if status == StatusCode::NOT_MODIFIED {
+                return Ok(None);
+            }
+
+            let headers = response.headers().clone();
+            let value = parse_response(
+                status,
+                &headers,
+                &response.bytes().await?,
+                self.inner.treat_html_as_unauthorized,
+                self.inner.untyped_fallback,
+            )?;
+
+            self.inner.validators.update(url.to_string(), &headers);
+
+            Ok(Some(value))
+        })
+        .await
+    }
+
+    async fn receive<T: DeserializeOwned>(&self, request: Request) -> Result<T> {
+        let response = self.respond(request).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+
+        parse_response(
+            status,
+            &headers,
+            &response.bytes().await?,
+            self.inner.treat_html_as_unauthorized,
+            self.inner.untyped_fallback,
+        )
+    }
+
+    /// Sends `request` like [`Client::send`], logging it and its response if
+    /// the `log` feature is enabled.
+    async fn respond(&self, request: Request) -> Result<Response> {
+        let url = request.url().clone();
+
+        sending(&url, &self.inner.token);
+
+        let response = self.send(request).await?;
+
+        received(&url, response.status());
+
+        Ok(response)
+    }
+
+    /// Sends `request`, retrying it if it fails transiently and retrying has
+    /// been enabled with [`ClientBuilder::retry`].
+    async fn send(&self, request: Request) -> reqwest::Result<Response> {
+        let mut backoff = Backoff::default();
+
+        loop {
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(rate_limiter) = &self.inner.rate_limiter {
+                retry::sleep(rate_limiter.reserve()).await;
+            }
+
+            let result = self
+                .inner
+                .transport
+                .get(
+                    request
+                        .try_clone()
+                        .expect("GET requests should not have a streamed body"),
+                )
+                .await;
+            let delay = backoff.next(
+                self.inner.retry,
+                result
+                    .as_ref()
+                    .map(|response| (response.status(), response.headers())),
+            );
+
+            let Some(delay) = delay else {
+                return result;
+            };
+
+            retry::sleep(delay).await;
+
+            if let Some(retries) = &self.retries {
+                retries.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Removes every response cached because of [`ClientBuilder::cache_ttl`].
+    ///
+    /// The cache is shared between clones of a client, so this clears it for
+    /// all of them.
+    #[cfg(feature = "cache")]
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.inner.cache {
+            cache.clear();
+        }
+    }
+
+    /// Checks `result` for `page` of `listing` like [`Client::members`] does.
+    fn page_in_range<T>(
+        &self,
+        listing: String,
+        result: Result<Page<T>>,
+        empty: ServerErrorKind,
+        page: u16,
+    ) -> Result<Page<T>> {
+        self.inner.last_pages.check(listing, result, empty, page)
+    }
+
+    /// Like [`Client::page_in_range`], but for pages fetched with
+    /// [`Client::get_if_modified`], which are [`None`] if unchanged.
+    fn modified_page_in_range<T>(
+        &self,
+        listing: String,
+        result: Result<Option<Page<T>>>,
+        empty: ServerErrorKind,
+        page: u16,
+    ) -> Result<Option<Page<T>>> {
+        result
+            .transpose()
+            .map(|result| self.page_in_range(listing, result, empty, page))
+            .transpose()
+    }
+
+    /// Calls `f` with a clone of this client and returns its output along with
+    /// how many times the requests it made were retried and how long it took.
+    ///
+    /// Useful for seeing how often requests fail transiently when tuning
+    /// [`ClientBuilder::retry`]. Retries of every request made through the
+    /// clone are counted, including concurrent ones.
+    ///
+    /// ```no_run
+    /// # async fn example(client: buy_me_a_coffee::Client) {
+    /// let (result, attempts) = client
+    ///     .with_attempts(|client| async move { client.all_supporters().await })
+    ///     .await;
+    ///
+    /// println!("{} retries in {:?}", attempts.retries, attempts.elapsed);
+    /// # }
+    /// ```
+    pub async fn with_attempts<T, F, Fut>(&self, f: F) -> (T, Attempts)
+    where
+        F: FnOnce(Client) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let retries = Arc::new(AtomicU32::new(0));
+        let client = Client {
+            retries: Some(retries.clone()),
+            ..self.clone()
+        };
+        let start = retry::now();
+        let output = f(client).await;
+        let attempts = Attempts {
+            retries: retries.load(Ordering::Relaxed),
+            elapsed: retry::now().duration_since(start).unwrap_or_default(),
+        };
+
+        (output, attempts)
+    }
+
+    /// Checks whether the API accepts the token, e.g. at startup before doing
+    /// any real work.
+    ///
+    /// Requests the first page of members, bypassing the cache, and returns
+    /// `Ok(true)` if it succeeds, including when there are no members.
+    /// Returns `Ok(false)` on [`Error::Unauthorized`], and any other error as
+    /// is, since it says nothing about the token.
+    pub async fn validate_token(&self) -> Result<bool> {
+        let endpoint = "/v1/subscriptions";
+        let request = self.request(endpoint, |request| {
+            self.page_query(request.query(&[("status", MemberStatus::All)]), 1)
+        })?;
+
+        match self.execute::<Page<Membership>>(endpoint, request).await {
+            Ok(_) => Ok(true),
+            Err(Error::Server(err)) if err.kind() == ServerErrorKind::NoSubscriptions => Ok(true),
+            Err(Error::Unauthorized { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Requests `endpoint`, e.g. `/v1/supporters`, with the `query` parameters
+    /// provided, and deserializes the response into `T`.
+    ///
+    /// This is an escape hatch for endpoints this crate doesn't support yet.
+    /// The request is authenticated, retried, and checked for errors like any
+    /// other, but its response is never cached.
+    pub async fn get_raw<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        query: &[(String, String)],
+    ) -> Result<T> {
+        let request = self.request(endpoint, |request| request.query(query))?;
+
+        self.execute(endpoint, request).await
+    }
+
+    /// Returns all members.
+    ///
+    /// If there are no members, returns an empty page rather than the
+    /// [`ServerErrorKind::NoSubscriptions`] error the API responds with. Use
+    /// [`Client::members_raw`] to receive that error instead. If `page` is
+    /// past the last page returned earlier, returns
+    /// [`Error::PageOutOfRange`] instead.
+    pub async fn members(&self, status: MemberStatus, page: u16) -> Result<Page<Membership>> {
+        self.page_in_range(
+            format!("/v1/subscriptions?status={status}"),
+            self.members_raw(status, page).await,
+            ServerErrorKind::NoSubscriptions,
+            page,
+        )
+    }
+
+    /// Like [`Client::members`], but if there are no members, returns
+    /// [`Error::Server`] with [`ServerError::kind`] being
+    /// [`ServerErrorKind::NoSubscriptions`], exactly as the API responds.
+    pub async fn members_raw(&self, status: MemberStatus, page: u16) -> Result<Page<Membership>> {
+        self.get("/v1/subscriptions", |request| {
+            self.page_query(request.query(&[("status", status)]), page)
+        })
+        .await
+    }
+
+    /// Returns the request [`Client::members`] would send, without sending
+    /// it.
+    ///
+    /// Useful for checking the URL, query string, and headers sent, e.g. in
+    /// tests without network access. Returns [`Error::Reqwest`] if the request
+    /// can't be built, which [`Client::members`] would fail with too.
+    pub fn build_members_request(&self, status: MemberStatus, page: u16) -> Result<Request> {
+        self.request("/v1/subscriptions", |request| {
+            self.page_query(request.query(&[("status", status)]), page)
+        })
+    }
+
+    /// Like [`Client::members`], but returns [`None`] if the page hasn't
+    /// changed since it was last requested with this method.
+    ///
+    /// If the API sent an `ETag` or `Last-Modified` header with the previous
+    /// response for the same page, it's sent back so that the API can respond
+    /// with 304 Not Modified instead of the whole page, which saves bandwidth
+    /// when polling. They're shared between clones of the client. Responses
+    /// are never cached with [`ClientBuilder::cache_ttl`].
+    pub async fn members_if_modified(
+        &self,
+        status: MemberStatus,
+        page: u16,
+    ) -> Result<Option<Page<Membership>>> {
+        let result = self
+            .get_if_modified("/v1/subscriptions", |request| {
+                self.page_query(request.query(&[("status", status)]), page)
+            })
+            .await;
+
+        self.modified_page_in_range(
+            format!("/v1/subscriptions?status={status}"),
+            result,
+            ServerErrorKind::NoSubscriptions,
+            page,
+        )
+    }
+
+    /// Returns a stream of all members, fetching pages as they are needed.
+    ///
+    /// Only one page is requested at a time, and the next page isn't requested
+    /// until every member of the previous one has been consumed. If there are
+    /// no members, the stream simply ends without yielding anything.
+    pub fn members_stream(
+        &self,
+        status: MemberStatus,
+    ) -> impl Stream<Item = Result<Membership>> + '_ {
+        page_stream(move |page| self.members(status, page))
+            .map_ok(|page| stream::iter(page.data.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Like [`Client::members_stream`], but fetches up to `prefetch` pages
+    /// ahead while the members of the current page are being yielded, to
+    /// reduce stalls between pages.
+    ///
+    /// Pages are still requested one at a time and members yielded in order.
+    /// A `prefetch` of zero behaves like [`Client::members_stream`]. The
+    /// trade-off is that if the stream is dropped before reaching the end, up
+    /// to `prefetch` pages may have been requested for nothing, which still
+    /// counts towards the API's rate limit.
+    pub fn members_stream_prefetch(
+        &self,
+        status: MemberStatus,
+        prefetch: usize,
+    ) -> impl Stream<Item = Result<Membership>> + '_ {
+        prefetch_items(
+            page_stream(move |page| self.members(status, page)),
+            prefetch,
+        )
+    }
+
+    /// Like [`Client::members_stream`], but yields whole pages, so that
+    /// [`Page::current_page`] and [`Page::last_page`] can be used to report
+    /// progress.
+    ///
+    /// Pages are yielded in order until the last one. If there are no
+    /// members, the stream simply ends without yielding anything.
+    pub fn member_page_stream(
+        &self,
+        status: MemberStatus,
+    ) -> impl Stream<Item = Result<Page<Membership>>> + '_ {
+        page_stream(move |page| self.members(status, page))
+    }
+
+    /// Returns a cursor that fetches one page of members each time
+    /// [`MemberPages::next_page`] is called, starting from the first.
+    pub fn member_pages(&self, status: MemberStatus) -> MemberPages<'_> {
+        MemberPages {
+            client: self,
+            status,
+            next: Some(1),
+        }
+    }
+
+    /// Returns all members across every page.
+    ///
+    /// Every page is fetched before returning, so all members are held in
+    /// memory at once. For accounts with many members, consider
+    /// [`Client::members_stream`] instead. If there are no members, returns an
+    /// empty [`Vec`].
+    pub async fn all_members(&self, status: MemberStatus) -> Result<Vec<Membership>> {
+        self.members_stream(status).try_collect().await
+    }
+
+    /// Like [`Client::all_members`], but fetches at most `max_pages` pages,
+    /// and reports whether more were left with [`Capped::truncated`].
+    ///
+    /// Useful for bounding how much memory and time walking a big account
+    /// takes.
+    pub async fn all_members_capped(
+        &self,
+        status: MemberStatus,
+        max_pages: u16,
+    ) -> Result<Capped<Membership>> {
+        collect_capped(|page| self.members(status, page), max_pages).await
+    }
+
+    /// Like [`Client::all_members`], but fails with [`Error::DeadlineExceeded`]
+    /// if every page hasn't been fetched by `deadline`.
+    ///
+    /// Unlike [`ClientBuilder::timeout`], which applies to each request, this
+    /// bounds the whole walk, aborting the request in progress once `deadline`
+    /// passes. Not available on WebAssembly.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn all_members_until(
+        &self,
+        status: MemberStatus,
+        deadline: Instant,
+    ) -> Result<Vec<Membership>> {
+        before(deadline, self.all_members(status)).await
+    }
+
+    /// Returns the first member whose [`Membership::payer_email`] matches
+    /// `email`, ignoring case, or [`None`] if there is none.
+    ///
+    /// Pages are fetched one at a time until a match is found.
+    pub async fn find_member_by_email(
+        &self,
+        status: MemberStatus,
+        email: &str,
+    ) -> Result<Option<Membership>> {
+        let email = email.to_lowercase();
+
+        Box::pin(
+            self.members_stream(status)
+                .try_filter(|membership| ready(membership.payer_email.to_lowercase() == email)),
+        )
+        .try_next()
+        .await
+    }
+
+    /// Returns the members who joined after `since`, i.e. whose
+    /// [`Membership::created_on`] is later.
+    ///
+    /// This assumes the API lists members newest first, which lets it stop
+    /// fetching pages at the first one with a member who joined before
+    /// `since`. If members turn out not to be ordered that way, it falls back
+    /// to fetching every page instead, so the result is complete either way
+    /// as long as any misordering shows up before that point.
+    #[cfg(feature = "chrono")]
+    pub async fn members_since(
+        &self,
+        status: MemberStatus,
+        since: Timestamp,
+    ) -> Result<Vec<Membership>> {
+        let mut pages = Box::pin(page_stream(|page| self.members(status, page)));
+        let mut members = Vec::new();
+        let mut previous: Option<Timestamp> = None;
+        let mut newest_first = true;
+
+        while let Some(page) = pages.try_next().await? {
+            let mut reached_since = false;
+
+            for membership in page.data {
+                newest_first &= previous.is_none_or(|previous| membership.created_on <= previous);
+                previous = Some(membership.created_on);
+
+                if membership.created_on > since {
+                    members.push(membership);
+                } else {
+                    reached_since = true;
+                }
+            }
+
+            if reached_since && newest_first {
+                break;
+            }
+        }
+
+        Ok(members)
+    }
+
+    /// Returns the total number of members, without fetching more than the
+    /// first page.
+    pub async fn members_count(&self, status: MemberStatus) -> Result<u16> {
+        Ok(self.members(status, 1).await?.total)
+    }
+
+    /// Returns how many members renew monthly, yearly, or otherwise, counted
+    /// by [`Membership::duration_type`] across every page.
+    pub async fn member_breakdown(&self, status: MemberStatus) -> Result<MemberBreakdown> {
+        page_stream(|page| self.members(status, page))
+            .try_fold(MemberBreakdown::default(), |mut breakdown, page| {
+                for membership in page.data {
+                    match membership.duration_type {
+                        DurationType::Month => breakdown.monthly += 1,
+                        DurationType::Year => breakdown.yearly += 1,
+                        DurationType::Other(_) => breakdown.other += 1,
+                    }
+                }
+
+                ready(Ok(breakdown))
+            })
+            .await
+    }
+
+    /// Returns every active member who cancelled at the end of the current
+    /// period, which hasn't ended yet, e.g. to remind them before they lapse.
+    ///
+    /// The API can't filter by cancellation, so this is done client-side after
+    /// fetching every page of active members.
+    #[cfg(feature = "chrono")]
+    pub async fn members_pending_cancellation(&self) -> Result<Vec<Membership>> {
+        page_stream(|page| self.members(MemberStatus::Active, page))
+            .map_ok(|page| {
+                page.data
+                    .into_iter()
+                    .filter(|membership| {
+                        membership.is_cancelled_at_period_end && !membership.is_expired()
+                    })
+                    .collect()
+            })
+            .try_concat()
+            .await
+    }
+
+    /// Returns the monthly recurring revenue from active members paying in
+    /// `currency` (e.g. `USD`, ignoring case).
+    ///
+    /// Each member counts for [`Membership::total_price`] if they renew
+    /// monthly, or a twelfth of it if they renew yearly. Members renewing in a
+    /// way this crate doesn't know about are left out, and so are members who
+    /// cancelled at the end of a period that has already ended, or whose price
+    /// couldn't be parsed. Nothing is
+    /// rounded: yearly amounts are divided with the full precision of
+    /// `rust_decimal`, so round the result for display, e.g. with
+    /// `Decimal::round_dp`.
+    #[cfg(all(feature = "chrono", feature = "rust_decimal"))]
+    pub async fn monthly_recurring_revenue(&self, currency: &str) -> Result<Price> {
+        page_stream(|page| self.members(MemberStatus::Active, page))
+            .try_fold(Price::ZERO, |total, page| {
+                ready(Ok(page
+                    .data
+                    .iter()
+                    .filter(|membership| {
+                        membership.currency.as_str().eq_ignore_ascii_case(currency)
+                            && !(membership.is_cancelled_at_period_end && membership.is_expired())
+                    })
+                    .filter_map(|membership| match membership.duration_type {
+                        DurationType::Month => membership.total_price(),
+                        DurationType::Year => membership
+                            .total_price()
+                            .map(|total_price| total_price / Price::from(12)),
+                        DurationType::Other(_) => None,
+                    })
+                    .fold(total, |total, amount| total + amount)))
+            })
+            .await
+    }
+
+    /// Returns membership details for the ID provided.
+    pub async fn membership(&self, id: u32) -> Result<Membership> {
+        self.get(&format!("/v1/subscriptions/{id}"), |request| request)
+            .await
+    }
+
+    /// Returns membership details for each of the IDs provided, in the same
+    /// order.
+    ///
+    /// Up to 4 memberships are requested at once. Each result stands on its
+    /// own, so an ID that doesn't exist doesn't prevent the others from being
+    /// returned. Likewise, requests that are rate limited fail with
+    /// [`Error::RateLimited`] without stopping the rest, so check for it if
+    /// resolving many IDs.
+    pub async fn memberships(&self, ids: &[u32]) -> Vec<Result<Membership>> {
+        stream::iter(ids)
+            .map(|&id| self.membership(id))
+            .buffered(MEMBERSHIPS_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Returns all onetime-supporters.
+    ///
+    /// If there are no supporters, returns an empty page rather than the
+    /// [`ServerErrorKind::NoSupporters`] error the API responds with. Use
+    /// [`Client::supporters_raw`] to receive that error instead. Like
+    /// [`Client::members`], returns [`Error::PageOutOfRange`] for pages known
+    /// to be past the last.
+    pub async fn supporters(&self, page: u16) -> Result<Page<Support>> {
+        self.list_supporters(ListParams::new().page(page)).await
+    }
+
+    /// Like [`Client::supporters`], but with the page, its size, and how its
+    /// supporters are sorted all set by `params`.
+    ///
+    /// Sorting only orders the supporters within the page fetched, see
+    /// [`ListParams::sort`]. Returns [`Error::InvalidConfig`] without making a
+    /// request if [`ListParams::per_page`] is zero, like
+    /// [`ClientBuilder::per_page`].
+    pub async fn list_supporters(&self, params: ListParams) -> Result<Page<Support>> {
+        if params.per_page == Some(0) {
+            return Err(Error::InvalidConfig("page size of zero".to_string()));
+        }
+
+        let listing = match params.per_page {
+            Some(per_page) => format!("/v1/supporters?per_page={per_page}"),
+            None => "/v1/supporters".to_string(),
+        };
+        let result = self
+            .get("/v1/supporters", |request| params.query(self, request))
+            .await;
+        let mut page =
+            self.page_in_range(listing, result, ServerErrorKind::NoSupporters, params.page)?;
+
+        if let Some((key, descending)) = params.sort {
+            sort_supporters(&mut page.data, key, descending);
+        }
+
+        Ok(page)
+    }
+
+    /// Like [`Client::supporters`], but if there are no supporters, returns
+    /// [`Error::Server`] with [`ServerError::kind`] being
+    /// [`ServerErrorKind::NoSupporters`], exactly as the API responds.
+    pub async fn supporters_raw(&self, page: u16) -> Result<Page<Support>> {
+        self.get("/v1/supporters", |request| self.page_query(request, page))
+            .await
+    }
+
+    /// Like [`Client::supporters`], but returns [`None`] if the page hasn't
+    /// changed since it was last requested with this method, like
+    /// [`Client::members_if_modified`].
+    pub async fn supporters_if_modified(&self, page: u16) -> Result<Option<Page<Support>>> {
+        let result = self
+            .get_if_modified("/v1/supporters", |request| self.page_query(request, page))
+            .await;
+
+        self.modified_page_in_range(
+            "/v1/supporters".to_string(),
+            result,
+            ServerErrorKind::NoSupporters,
+            page,
+        )
+    }
+
+    /// Returns a cursor that fetches one page of onetime-supporters each time
+    /// [`SupporterPages::next_page`] is called, starting from the first.
+    pub fn supporter_pages(&self) -> SupporterPages<'_> {
+        SupporterPages {
+            client: self,
+            next: Some(1),
+        }
+    }
+
+    /// Returns all onetime-supporters across every page.
+    ///
+    /// Every page is fetched before returning, so all supporters are held in
+    /// memory at once. If there are no supporters, returns an empty [`Vec`].
+    pub async fn all_supporters(&self) -> Result<Vec<Support>> {
+        page_stream(|page| self.supporters(page))
+            .map_ok(|page| page.data)
+            .try_concat()
+            .await
+    }
+
+    /// Like [`Client::all_supporters`], but fetches at most `max_pages` pages,
+    /// and reports whether more were left with [`Capped::truncated`].
+    pub async fn all_supporters_capped(&self, max_pages: u16) -> Result<Capped<Support>> {
+        collect_capped(|page| self.supporters(page), max_pages).await
+    }
+
+    /// Like [`Client::all_supporters`], but fails with
+    /// [`Error::DeadlineExceeded`] if every page hasn't been fetched by
+    /// `deadline`, like [`Client::all_members_until`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn all_supporters_until(&self, deadline: Instant) -> Result<Vec<Support>> {
+        before(deadline, self.all_supporters()).await
+    }
+
+    /// Returns the `limit` most recent onetime-supporters, or all of them if
+    /// there are fewer.
+    ///
+    /// The API lists supporters newest first, so pages are only fetched until
+    /// `limit` supporters have been collected. If there are no supporters,
+    /// returns an empty [`Vec`].
+    pub async fn recent_supporters(&self, limit: usize) -> Result<Vec<Support>> {
+        let mut pages = Box::pin(page_stream(|page| self.supporters(page)));
+        let mut supporters = Vec::new();
+
+        while supporters.len() < limit {
+            let Some(page) = pages.try_next().await? else {
+                break;
+            };
+
+            supporters.extend(page.data);
+        }
+
+        supporters.truncate(limit);
+
+        Ok(supporters)
+    }
+
+    /// Returns the onetime-supporters who supported from `start` to `end`,
+    /// both inclusive, by [`Support::created_on`], e.g. for a monthly report.
+    ///
+    /// Like [`Client::members_since`], this assumes the API lists supporters
+    /// newest first, which lets it stop fetching pages at the first one with
+    /// a supporter from before `start`. If supporters turn out not to be
+    /// ordered that way, it falls back to fetching every page instead.
+    /// Supporters are returned in the order the API returns them.
+    #[cfg(feature = "chrono")]
+    pub async fn supporters_between(
+        &self,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> Result<Vec<Support>> {
+        let mut pages = Box::pin(page_stream(|page| self.supporters(page)));
+        let mut supporters = Vec::new();
+        let mut previous: Option<Timestamp> = None;
+        let mut newest_first = true;
+
+        while let Some(page) = pages.try_next().await? {
+            let mut reached_start = false;
+
+            for support in page.data {
+                newest_first &= previous.is_none_or(|previous| support.created_on <= previous);
+                previous = Some(support.created_on);
+
+                if support.created_on < start {
+                    reached_start = true;
+                } else if support.created_on <= end {
+                    supporters.push(support);
+                }
+            }
+
+            if reached_start && newest_first {
+                break;
+            }
+        }
+
+        Ok(supporters)
+    }
+
+    /// Like [`Client::all_supporters`], but fetches up to `concurrency` pages
+    /// at once after the first, which is much faster when there are many.
+    ///
+    /// Supporters are still returned in page order. If any request fails, the
+    /// others still in flight are dropped and the error is returned. Each
+    /// request is retried as configured with [`ClientBuilder::retry`], but
+    /// fetching many pages at once makes [`Error::RateLimited`] more likely, so
+    /// keep `concurrency` modest. A `concurrency` of `0` is treated as `1`.
+    pub async fn all_supporters_concurrent(&self, concurrency: usize) -> Result<Vec<Support>> {
+        let first = self.supporters(1).await?;
+
+        if first.data.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rest: Vec<_> = stream::iter(2..=first.last_page)
+            .map(|page| self.supporters(page))
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        Ok(first
+            .data
+            .into_iter()
+            .chain(rest.into_iter().flat_map(|page| page.data))
+            .collect())
+    }
+
+    /// Returns every onetime-supporter whose support was refunded.
+    ///
+    /// Refunds are usually rare, but the API can't filter them, so this still
+    /// fetches every page of supporters.
+    pub async fn refunded_supporters(&self) -> Result<Vec<Support>> {
+        page_stream(|page| self.supporters(page))
+            .map_ok(|page| {
+                page.data
+                    .into_iter()
+                    .filter(|support| support.is_refunded)
+                    .collect()
+            })
+            .try_concat()
+            .await
+    }
+
+    /// Returns every onetime-supporter who bought at least `min` coffees, in
+    /// the order the API returns them.
+    ///
+    /// The API can't filter by coffees, so this is done client-side after
+    /// fetching every page of supporters.
+    pub async fn supporters_min_coffees(&self, min: u16) -> Result<Vec<Support>> {
+        page_stream(|page| self.supporters(page))
+            .map_ok(|page| {
+                page.data
+                    .into_iter()
+                    .filter(|support| support.coffee_num >= u32::from(min))
+                    .collect()
+            })
+            .try_concat()
+            .await
+    }
+
+    /// Returns every onetime-supporter sorted by `key`, in descending order if
+    /// `descending` is set, e.g. for a leaderboard.
+    ///
+    /// Every page is fetched and sorted in memory, which is fine for accounts
+    /// of a moderate size. Supports that are equal by `key` keep the order the
+    /// API returned them in.
+    pub async fn supporters_sorted(
+        &self,
+        key: SupportSortKey,
+        descending: bool,
+    ) -> Result<Vec<Support>> {
+        let mut supporters = self.all_supporters().await?;
+
+        sort_supporters(&mut supporters, key, descending);
+
+        Ok(supporters)
+    }
+
+    /// Returns every onetime-supporter grouped by [`Support::country`], keyed
+    /// by its code, e.g. `US`. Supporters without a country are grouped under
+    /// `unknown`.
+    ///
+    /// Every page is fetched before returning, so all supporters are held in
+    /// memory at once. Within each group, supporters are in the order the API
+    /// returns them.
+    pub async fn supporters_by_country(&self) -> Result<HashMap<String, Vec<Support>>> {
+        page_stream(|page| self.supporters(page))
+            .try_fold(HashMap::new(), |mut groups, page| {
+                for support in page.data {
+                    let country = match &support.country {
+                        Some(country) => country.to_string(),
+                        None => UNKNOWN_COUNTRY.to_string(),
+                    };
+
+                    groups.entry(country).or_insert_with(Vec::new).push(support);
+                }
+
+                ready(Ok(groups))
+            })
+            .await
+    }
+
+    /// Returns the total amount raised from onetime-supporters, summing
+    /// [`Support::total_price`] across every page and excluding refunded
+    /// supports, as well as supports whose price couldn't be parsed.
+    ///
+    /// If `currency` is given, only supports in that currency (e.g. `USD`) are
+    /// counted. Amounts in different currencies are added up as they are, so
+    /// accounts receiving more than one currency should pass a filter to get a
+    /// meaningful sum.
+    #[cfg(feature = "rust_decimal")]
+    pub async fn total_support_amount(&self, currency: Option<&str>) -> Result<Price> {
+        page_stream(|page| self.supporters(page))
+            .try_fold(Price::ZERO, |total, page| {
+                ready(Ok(page
+                    .data
+                    .iter()
+                    .filter(|support| !support.is_refunded)
+                    .filter(|support| {
+                        currency.is_none_or(|currency| {
+                            support.currency.as_str().eq_ignore_ascii_case(currency)
+                        })
+                    })
+                    .filter_map(Support::total_price)
+                    .fold(total, |total, amount| total + amount)))
+            })
+            .await
+    }
+
+    /// Returns the total number of onetime-supporters, without fetching more
+    /// than the first page.
+    pub async fn supporters_count(&self) -> Result<u16> {
+        Ok(self.supporters(1).await?.total)
+    }
+
+    /// Returns details on support with the ID provided.
+    pub async fn support(&self, id: u32) -> Result<Support> {
+        self.get(&format!("/v1/supporters/{id}"), |request| request)
+            .await
+    }
+
+    /// Returns details on support with the ID provided, along with the
+    /// membership of the same payer, if any.
+    ///
+    /// The membership is found with [`Client::find_member_by_email`] using
+    /// [`Support::payer_email`], among members of any status. The API can't
+    /// look members up by email, so this fetches pages of members one at a
+    /// time until a match is found, which means every page when there is
+    /// none. Supports without a payer email never have a membership.
+    pub async fn support_with_membership(&self, id: u32) -> Result<(Support, Option<Membership>)> {
+        let support = self.support(id).await?;
+
+        if support.payer_email.trim().is_empty() {
+            return Ok((support, None));
+        }
+
+        let membership = self
+            .find_member_by_email(MemberStatus::All, &support.payer_email)
+            .await?;
+
+        Ok((support, membership))
+    }
+
+    /// Returns the distinct payer emails of every member, of any status, and
+    /// onetime-supporter, e.g. for cross-referencing one-time and recurring
+    /// supporters.
+    ///
+    /// Emails are trimmed and lowercased, so the same payer isn't counted
+    /// twice for writing their email differently. Empty emails are left out.
+    /// Members and supporters are fetched concurrently, every page of both.
+    pub async fn unique_payers(&self) -> Result<HashSet<String>> {
+        let (members, supporters) =
+            futures::future::try_join(self.all_members(MemberStatus::All), self.all_supporters())
+                .await?;
+
+        Ok(members
+            .iter()
+            .map(|membership| &membership.payer_email)
+            .chain(supporters.iter().map(|support| &support.payer_email))
+            .map(|email| email.trim().to_lowercase())
+            .filter(|email| !email.is_empty())
+            .collect())
+    }
+
+    /// Returns all extra purchases.
+    ///
+    /// If there are no extra purchases, returns an empty page rather than the
+    /// [`ServerErrorKind::NoExtraPurchases`] error the API responds with. Use
+    /// [`Client::extras_raw`] to receive that error instead. Like
+    /// [`Client::members`], returns [`Error::PageOutOfRange`] for pages known
+    /// to be past the last.
+    pub async fn extras(&self, page: u16) -> Result<Page<Purchase>> {
+        self.page_in_range(
+            "/v1/extras".to_string(),
+            self.extras_raw(page).await,
+            ServerErrorKind::NoExtraPurchases,
+            page,
+        )
+    }
+
+    /// Like [`Client::extras`], but if there are no extra purchases, returns
+    /// [`Error::Server`] with [`ServerError::kind`] being
+    /// [`ServerErrorKind::NoExtraPurchases`], exactly as the API responds.
+    pub async fn extras_raw(&self, page: u16) -> Result<Page<Purchase>> {
+        self.get("/v1/extras", |request| self.page_query(request, page))
+            .await
+    }
+
+    /// Like [`Client::extras`], but returns [`None`] if the page hasn't changed
+    /// since it was last requested with this method, like
+    /// [`Client::members_if_modified`].
+    pub async fn extras_if_modified(&self, page: u16) -> Result<Option<Page<Purchase>>> {
+        let result = self
+            .get_if_modified("/v1/extras", |request| self.page_query(request, page))
+            .await;
+
+        self.modified_page_in_range(
+            "/v1/extras".to_string(),
+            result,
+            ServerErrorKind::NoExtraPurchases,
+            page,
+        )
+    }
+
+    /// Returns a cursor that fetches one page of extra purchases each time
+    /// [`ExtraPages::next_page`] is called, starting from the first.
+    pub fn extra_pages(&self) -> ExtraPages<'_> {
+        ExtraPages {
+            client: self,
+            next: Some(1),
+        }
+    }
+
+    /// Returns all extra purchases across every page.
+    ///
+    /// Every page is fetched before returning, so all extra purchases are held
+    /// in memory at once. If there are no extra purchases, returns an empty
+    /// [`Vec`].
+    pub async fn all_extras(&self) -> Result<Vec<Purchase>> {
+        page_stream(|page| self.extras(page))
+            .map_ok(|page| page.data)
+            .try_concat()
+            .await
+    }
+
+    /// Like [`Client::all_extras`], but fetches at most `max_pages` pages, and
+    /// reports whether more were left with [`Capped::truncated`].
+    pub async fn all_extras_capped(&self, max_pages: u16) -> Result<Capped<Purchase>> {
+        collect_capped(|page| self.extras(page), max_pages).await
+    }
+
+    /// Like [`Client::all_extras`], but fails with [`Error::DeadlineExceeded`]
+    /// if every page hasn't been fetched by `deadline`, like
+    /// [`Client::all_members_until`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn all_extras_until(&self, deadline: Instant) -> Result<Vec<Purchase>> {
+        before(deadline, self.all_extras()).await
+    }
+
+    /// Returns every extra purchase of the extra with the ID provided, i.e.
+    /// whose [`Extra::id`] is `reward_id`.
+    ///
+    /// The API can't filter purchases by extra, so this fetches every page of
+    /// extra purchases and filters them here.
+    pub async fn extras_for_reward(&self, reward_id: u32) -> Result<Vec<Purchase>> {
+        page_stream(|page| self.extras(page))
+            .map_ok(|page| {
+                page.data
+                    .into_iter()
+                    .filter(|purchase| purchase.extra.id == reward_id)
+                    .collect()
+            })
+            .try_concat()
+            .await
+    }
+
+    /// Returns every extra that has been purchased, once each, sorted by
+    /// [`Extra::id`].
+    ///
+    /// The API has no endpoint for extras themselves, so this fetches every
+    /// page of extra purchases and keeps one [`Purchase::extra`] per ID. If
+    /// purchases embed different versions of the same extra, the one with the
+    /// latest [`Extra::updated_on`] is kept. Extras that were never purchased
+    /// can't be returned.
+    pub async fn reward_definitions(&self) -> Result<Vec<Extra>> {
+        let extras = page_stream(|page| self.extras(page))
+            .try_fold(HashMap::<u32, Extra>::new(), |mut extras, page| {
+                for purchase in page.data {
+                    let extra = purchase.extra;
+
+                    match extras.get(&extra.id) {
+                        Some(kept) if kept.updated_on >= extra.updated_on => {}
+                        _ => {
+                            extras.insert(extra.id, extra);
+                        }
+                    }
+                }
+
+                ready(Ok(extras))
+            })
+            .await?;
+        let mut extras: Vec<_> = extras.into_values().collect();
+
+        extras.sort_by_key(|extra| extra.id);
+
+        Ok(extras)
+    }
+
+    /// Returns whether the extra with the ID provided is active and how many
+    /// slots it has left, or [`None`] if it was never purchased.
+    ///
+    /// The API has no endpoint for extras themselves, so the extra is looked
+    /// up among [`Client::reward_definitions`], which fetches every page of
+    /// extra purchases. Extras that were never purchased can't be found.
+    pub async fn reward_availability(&self, reward_id: u32) -> Result<Option<RewardAvailability>> {
+        Ok(self
+            .reward_definitions()
+            .await?
+            .into_iter()
+            .find(|extra| extra.id == reward_id)
+            .map(|extra| RewardAvailability {
+                is_active: extra.is_active,
+                is_deleted: extra.deleted_on.is_some(),
+                remaining_slots: extra.remaining_slots(),
+            }))
+    }
+
+    /// Returns every member, onetime-supporter, and extra purchase at once,
+    /// e.g. for loading a dashboard.
+    ///
+    /// The three listings are walked concurrently, each fetching every one of
+    /// its pages, so this may make many requests. They all go through the
+    /// same client, so a limit set with [`ClientBuilder::rate_limit`] applies
+    /// to them together. Listings with nothing in them are empty rather than
+    /// errors. Fails as soon as any request does.
+    pub async fn snapshot(&self) -> Result<Snapshot> {
+        let (members, supporters, extras) = futures::future::try_join3(
+            self.all_members(MemberStatus::All),
+            self.all_supporters(),
+            self.all_extras(),
+        )
+        .await?;
+
+        Ok(Snapshot {
+            members,
+            supporters,
+            extras,
+        })
+    }
+
+    /// Returns the total number of extra purchases, without fetching more
+    /// than the first page.
+    pub async fn extras_count(&self) -> Result<u16> {
+        Ok(self.extras(1).await?.total)
+    }
+
+    /// Returns details on an extra purchase with the ID provided.
+    ///
+    /// Note that `id` is [`Purchase::id`], not [`Extra::id`].
+    pub async fn extra(&self, id: u32) -> Result<Purchase> {
+        self.get(&format!("/v1/extras/{id}"), |request| request)
+            .await
+    }
+}
 
-use std::fmt::{self, Debug, Formatter};
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum MemberStatus {
+    Active,
+    Inactive,
+    All,
+}
 
-use reqwest::{
-    header::{CONTENT_TYPE, USER_AGENT},
-    RequestBuilder, StatusCode,
-};
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use thiserror::Error;
+impl MemberStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MemberStatus::Active => "active",
+            MemberStatus::Inactive => "inactive",
+            MemberStatus::All => "all",
+        }
+    }
+}
 
-const PREFIX: &str = "https://developers.buymeacoffee.com/api";
-const USER_AGENT_VALUE: &str = "buy-me-a-coffee-rs/0.1.0";
+impl Display for MemberStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-pub type Result<T> = std::result::Result<T, Error>;
+/// Parses "active", "inactive", or "all", ignoring case and surrounding
+/// whitespace.
+impl FromStr for MemberStatus {
+    type Err = ParseMemberStatusError;
 
-#[derive(Debug, Error)]
-pub enum Error {
-    #[error(transparent)]
-    Reqwest(#[from] reqwest::Error),
-    #[error("{0}")]
-    Client(StatusCode),
-    #[error(transparent)]
-    Server(#[from] ServerError),
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "active" => Ok(MemberStatus::Active),
+            "inactive" => Ok(MemberStatus::Inactive),
+            "all" => Ok(MemberStatus::All),
+            _ => Err(ParseMemberStatusError(s.to_string())),
+        }
+    }
 }
 
-#[derive(Debug, Error, Deserialize)]
-#[error("{}{reason}", if let Some(error_code) = .error_code { format!("{error_code} ") } else { "".to_string() })]
-pub struct ServerError {
-    pub error_code: Option<u16>,
-    #[serde(alias = "error")]
-    pub reason: String,
-}
+/// Returned when parsing a [`MemberStatus`] from a string that isn't
+/// "active", "inactive", or "all".
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("unknown member status \"{0}\", expected \"active\", \"inactive\", or \"all\"")]
+pub struct ParseMemberStatusError(pub String);
 
-#[derive(Deserialize)]
-#[serde(untagged)]
-enum UntaggedResult<T> {
-    Err(ServerError),
-    Ok(T),
+/// How many members renew how often, as returned by
+/// [`Client::member_breakdown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MemberBreakdown {
+    /// Members whose membership renews every month.
+    pub monthly: u32,
+    /// Members whose membership renews every year.
+    pub yearly: u32,
+    /// Members whose membership renews in a way this crate doesn't know
+    /// about yet.
+    pub other: u32,
 }
 
-impl<T> Into<Result<T>> for UntaggedResult<T> {
-    fn into(self) -> Result<T> {
-        match self {
-            UntaggedResult::Err(err) => Err(Error::Server(err)),
-            UntaggedResult::Ok(t) => Ok(t),
-        }
+impl MemberBreakdown {
+    /// Returns the number of members counted.
+    pub fn total(&self) -> u32 {
+        self.monthly + self.yearly + self.other
     }
 }
 
-#[derive(Clone)]
-pub struct Client {
-    client: reqwest::Client,
-    token: String,
+/// The items of up to a maximum number of pages, as returned by
+/// [`Client::all_members_capped`], [`Client::all_supporters_capped`], and
+/// [`Client::all_extras_capped`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Capped<T> {
+    /// The items of every page fetched, in order.
+    pub data: Vec<T>,
+    /// Whether there are pages left that weren't fetched because the maximum
+    /// was reached. A maximum of zero pages fetches nothing and is always
+    /// truncated, since there might be items.
+    pub truncated: bool,
 }
 
-impl Debug for Client {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Client")
-            .field("client", &self.client)
-            .field("token", &String::from_iter(vec!['*'; self.token.len()]))
-            .finish()
+/// Whether an extra can be purchased, as returned by
+/// [`Client::reward_availability`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RewardAvailability {
+    /// See [`Extra::is_active`].
+    pub is_active: bool,
+    /// Whether [`Extra::deleted_on`] is set.
+    pub is_deleted: bool,
+    /// See [`Extra::remaining_slots`].
+    pub remaining_slots: Option<u32>,
+}
+
+impl RewardAvailability {
+    /// Returns whether the extra is active, not deleted, and has slots left,
+    /// if they're limited.
+    pub fn is_purchasable(&self) -> bool {
+        self.is_active && !self.is_deleted && self.remaining_slots != Some(0)
     }
 }
 
-impl Client {
-    pub fn new(token: impl ToString) -> Self {
-        Self {
-            client: reqwest::Client::new(),
-            token: token.to_string(),
+/// Every member, onetime-supporter, and extra purchase, as returned by
+/// [`Client::snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Members of any status.
+    pub members: Vec<Membership>,
+    pub supporters: Vec<Support>,
+    pub extras: Vec<Purchase>,
+}
+
+impl Snapshot {
+    /// Returns what changed since the `previous` snapshot, for notifying
+    /// about changes without webhooks.
+    ///
+    /// Items are matched up by ID. Anything that's in this snapshot but not
+    /// in `previous` is new, so a onetime-supporter who was refunded between
+    /// the two is both added and refunded. Items are listed in the order of
+    /// this snapshot.
+    pub fn diff(&self, previous: &Snapshot) -> SnapshotDiff {
+        let members: HashMap<_, _> = previous
+            .members
+            .iter()
+            .map(|membership| (membership.id, membership))
+            .collect();
+        let supporters: HashMap<_, _> = previous
+            .supporters
+            .iter()
+            .map(|support| (support.id, support))
+            .collect();
+        let is_cancelled = |membership: &Membership| {
+            membership.is_cancelled || membership.is_cancelled_at_period_end
+        };
+
+        SnapshotDiff {
+            added_supporters: self
+                .supporters
+                .iter()
+                .filter(|support| !supporters.contains_key(&support.id))
+                .cloned()
+                .collect(),
+            refunded_supporters: self
+                .supporters
+                .iter()
+                .filter(|support| {
+                    support.is_refunded
+                        && !supporters
+                            .get(&support.id)
+                            .is_some_and(|previous| previous.is_refunded)
+                })
+                .cloned()
+                .collect(),
+            cancelled_members: self
+                .members
+                .iter()
+                .filter(|membership| {
+                    is_cancelled(membership)
+                        && !members
+                            .get(&membership.id)
+                            .is_some_and(|previous| is_cancelled(previous))
+                })
+                .cloned()
+                .collect(),
         }
     }
+}
 
-    async fn get<T: DeserializeOwned>(
-        &self,
-        endpoint: &str,
-        modify_request: impl FnOnce(RequestBuilder) -> RequestBuilder,
-    ) -> Result<T> {
-        let mut request = self
-            .client
-            .get(format!("{PREFIX}{endpoint}"))
-            .bearer_auth(&self.token)
-            .header(USER_AGENT, USER_AGENT_VALUE);
+/// The changes between two [`Snapshot`]s, as returned by [`Snapshot::diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SnapshotDiff {
+    /// Onetime-supporters that weren't in the previous snapshot.
+    pub added_supporters: Vec<Support>,
+    /// Onetime-supporters that are refunded now but weren't before.
+    pub refunded_supporters: Vec<Support>,
+    /// Members that are cancelled now, immediately or at the end of the
+    /// period, but weren't before.
+    pub cancelled_members: Vec<Membership>,
+}
 
-        request = modify_request(request);
+impl SnapshotDiff {
+    /// Returns whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added_supporters.is_empty()
+            && self.refunded_supporters.is_empty()
+            && self.cancelled_members.is_empty()
+    }
+}
 
-        let response = request.send().await?;
+/// What [`Client::supporters_sorted`] sorts onetime-supporters by.
+///
+/// Some keys are only available with the features they need, so matching on
+/// this requires a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SupportSortKey {
+    /// [`Support::created_on`], oldest first. Requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    CreatedOn,
+    /// [`Support::coffee_num`], fewest first.
+    Coffees,
+    /// [`Support::total_price`], smallest first, with prices that couldn't be
+    /// parsed before any other. Amounts in different currencies are compared
+    /// as they are. Requires the `rust_decimal` feature.
+    #[cfg(feature = "rust_decimal")]
+    Amount,
+}
 
-        // For some reason, when unauthorized, the API will redirect to the
-        // login page, despite the agent not being a browser. This is annoying,
-        // but consistent enough that we can anticipate it and turn it into an
-        // error that makes sense.
-        if response
-            .headers()
-            .get(CONTENT_TYPE)
-            .and_then(|content_type| {
-                content_type
-                    .to_str()
-                    .map(|content_type_str| content_type_str.contains("html"))
-                    .ok()
-            })
-            .unwrap_or_default()
-        {
-            return Err(Error::Client(StatusCode::UNAUTHORIZED));
+impl SupportSortKey {
+    /// Compares `a` to `b` by this key, in ascending order.
+    pub fn compare(self, a: &Support, b: &Support) -> std::cmp::Ordering {
+        match self {
+            #[cfg(feature = "chrono")]
+            SupportSortKey::CreatedOn => a.created_on.cmp(&b.created_on),
+            SupportSortKey::Coffees => a.coffee_num.cmp(&b.coffee_num),
+            #[cfg(feature = "rust_decimal")]
+            SupportSortKey::Amount => a.total_price().cmp(&b.total_price()),
         }
+    }
+}
 
-        let status = response.status();
+/// Sorts `supporters` by `key`, in descending order if `descending` is set,
+/// keeping the order of supports that are equal by `key`.
+fn sort_supporters(supporters: &mut [Support], key: SupportSortKey, descending: bool) {
+    supporters.sort_by(|a, b| {
+        let ordering = key.compare(a, b);
 
-        if status.is_client_error() {
-            return Err(Error::Client(status));
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
         }
+    });
+}
 
-        response.json::<UntaggedResult<T>>().await?.into()
-    }
+/// Which page of a listing to fetch with [`Client::list_supporters`], and how.
+///
+/// The API honors `page` and `per_page`, which are sent as query parameters of
+/// the same names. It can't sort listings, so sorting is done after the page
+/// is fetched.
+///
+/// ```no_run
+/// # async fn example(client: buy_me_a_coffee::Client) -> buy_me_a_coffee::Result<()> {
+/// use buy_me_a_coffee::{ListParams, SupportSortKey};
+///
+/// let page = client
+///     .list_supporters(
+///         ListParams::new()
+///             .page(2)
+///             .per_page(50)
+///             .sort(SupportSortKey::Coffees, true),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListParams {
+    page: u16,
+    per_page: Option<u16>,
+    sort: Option<(SupportSortKey, bool)>,
+}
 
-    /// Returns all members.
-    ///
-    /// If there are no members, returns [`Error::Server`] with
-    /// [`ServerError::reason`] being "No subscriptions".
-    pub async fn members(&self, status: MemberStatus, page: u16) -> Result<Page<Membership>> {
-        self.get("/v1/subscriptions", |request| {
-            request
-                .query(&[("status", status)])
-                .query(&[("page", page)])
-        })
-        .await
+impl Default for ListParams {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    /// Returns membership details for the ID provided.
-    pub async fn membership(&self, id: u32) -> Result<Membership> {
-        self.get(&format!("/v1/subscriptions/{id}"), |request| request)
-            .await
+impl ListParams {
+    /// Selects the first page, of the size set with
+    /// [`ClientBuilder::per_page`] or the API's default, unsorted.
+    pub fn new() -> Self {
+        Self {
+            page: 1,
+            per_page: None,
+            sort: None,
+        }
     }
 
-    /// Returns all onetime-supporters.
-    ///
-    /// If there are no supporters, returns [`Error::Server`] with
-    /// [`ServerError::reason`] being "No supporters".
-    pub async fn supporters(&self, page: u16) -> Result<Page<Support>> {
-        self.get("/v1/supporters", |request| request.query(&[("page", page)]))
-            .await
+    /// Sets which page to fetch, starting from `1`.
+    pub fn page(mut self, page: u16) -> Self {
+        self.page = page;
+        self
     }
 
-    /// Returns details on support with the ID provided.
-    pub async fn support(&self, id: u32) -> Result<Support> {
-        self.get(&format!("/v1/supporters/{id}"), |request| request)
-            .await
+    /// Sets how many items the page holds, instead of the size set with
+    /// [`ClientBuilder::per_page`]. The API may cap it, like with that
+    /// method. Listing fails with [`Error::InvalidConfig`] if `per_page` is
+    /// zero.
+    pub fn per_page(mut self, per_page: u16) -> Self {
+        self.per_page = Some(per_page);
+        self
     }
 
-    /// Returns all extra purchases.
+    /// Sorts the items of the page by `key`, in descending order if
+    /// `descending` is set, like [`Client::supporters_sorted`].
     ///
-    /// If there are no extra purchases, returns [`Error::Server`] with
-    /// [`ServerError::reason`] being "No extra purchases".
-    pub async fn extras(&self, page: u16) -> Result<Page<Purchase>> {
-        self.get("/v1/extras", |request| request.query(&[("page", page)]))
-            .await
+    /// This isn't a server-side sort: the API can't sort, so only the items
+    /// of the page fetched are ordered, after fetching it. Sorting different
+    /// pages doesn't make them continue from one another.
+    pub fn sort(mut self, key: SupportSortKey, descending: bool) -> Self {
+        self.sort = Some((key, descending));
+        self
     }
 
-    /// Returns details on an extra purchase with the ID provided.
-    ///
-    /// Note that `id` is [`Purchase::id`], not [`Extra::id`].
-    pub async fn extra(&self, id: u32) -> Result<Purchase> {
-        self.get(&format!("/v1/extras/{id}"), |request| request)
-            .await
+    /// Adds the query parameters selecting the page, falling back to the size
+    /// set for `client` if there is none.
+    fn query(&self, client: &Client, request: RequestBuilder) -> RequestBuilder {
+        request.query(&page_params(
+            self.page,
+            self.per_page.or(client.inner.per_page),
+        ))
     }
 }
 
-#[derive(Debug, Serialize, Clone, Copy)]
-#[serde(rename_all = "lowercase")]
-pub enum MemberStatus {
-    Active,
-    Inactive,
-    All,
-}
-
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Page<T> {
     pub current_page: u16,
     pub data: Vec<T>,
@@ -219,98 +2126,584 @@ pub struct Page<T> {
 // TODO: Implement [`AsyncIterator`] to iterate over pages when it has matured
 //       enough
 
-#[derive(Debug, Deserialize)]
+impl<T> Page<T> {
+    /// Returns a page without any items, like the ones the API would return
+    /// for an empty account if it didn't respond with an error instead.
+    fn empty(current_page: u16) -> Self {
+        Self {
+            current_page,
+            data: Vec::new(),
+            from: 0,
+            last_page: 1,
+            per_page: 0,
+            to: 0,
+            total: 0,
+        }
+    }
+
+    /// Returns whether there's a page after this one.
+    pub fn has_next(&self) -> bool {
+        self.current_page < self.last_page
+    }
+
+    /// Returns whether there's a page before this one.
+    pub fn has_previous(&self) -> bool {
+        self.current_page > 1
+    }
+
+    /// Returns whether this page has no items.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+/// Walks pages one after another, starting from the first, until the last page
+/// or an empty page has been yielded. Empty pages are not yielded.
+fn page_stream<T, F, Fut>(fetch: F) -> impl Stream<Item = Result<Page<T>>>
+where
+    F: FnMut(u16) -> Fut,
+    Fut: Future<Output = Result<Page<T>>>,
+{
+    stream::unfold((Some(1), fetch), move |(next, mut fetch)| async move {
+        let page_num = next?;
+
+        match fetch(page_num).await {
+            Ok(page) if page.is_empty() => None,
+            Ok(page) => {
+                let next = page.has_next().then(|| page.current_page + 1);
+
+                Some((Ok(page), (next, fetch)))
+            }
+            Err(err) => Some((Err(err), (None, fetch))),
+        }
+    })
+}
+
+/// Runs `future`, failing with [`Error::DeadlineExceeded`] if it hasn't
+/// finished by `deadline`.
+#[cfg(not(target_arch = "wasm32"))]
+async fn before<T>(deadline: Instant, future: impl Future<Output = Result<T>>) -> Result<T> {
+    tokio::time::timeout_at(deadline.into(), future)
+        .await
+        .unwrap_or(Err(Error::DeadlineExceeded))
+}
+
+/// Collects the items of the pages walked by [`page_stream`], stopping after
+/// `max_pages` pages.
+async fn collect_capped<T, F, Fut>(fetch: F, max_pages: u16) -> Result<Capped<T>>
+where
+    F: FnMut(u16) -> Fut,
+    Fut: Future<Output = Result<Page<T>>>,
+{
+    let mut pages = Box::pin(page_stream(fetch).take(max_pages.into()));
+    let mut capped = Capped {
+        data: Vec::new(),
+        truncated: max_pages == 0,
+    };
+
+    while let Some(page) = pages.try_next().await? {
+        capped.truncated = page.has_next();
+        capped.data.extend(page.data);
+    }
+
+    Ok(capped)
+}
+
+/// Yields the items of every page of `pages` in order, polling `pages` for up
+/// to `prefetch` pages ahead of the one whose items are being yielded.
+fn prefetch_items<T, S>(pages: S, prefetch: usize) -> impl Stream<Item = Result<T>>
+where
+    S: Stream<Item = Result<Page<T>>>,
+{
+    let mut pages = Box::pin(pages.fuse());
+    let mut fetched = VecDeque::new();
+    let mut items = Vec::new().into_iter();
+
+    stream::poll_fn(move |cx| {
+        while fetched.len() <= prefetch && !pages.is_terminated() {
+            match pages.as_mut().poll_next(cx) {
+                Poll::Ready(Some(page)) => fetched.push_back(page),
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+
+        loop {
+            if let Some(item) = items.next() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            match fetched.pop_front() {
+                Some(Ok(page)) => items = page.data.into_iter(),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None if pages.is_terminated() => return Poll::Ready(None),
+                None => return Poll::Pending,
+            }
+        }
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Membership {
     #[serde(rename = "subscription_id")]
     pub id: u32,
-    #[serde(rename = "subscription_cancelled_on")]
-    pub cancelled_on: Option<String>,
+    #[serde(rename = "subscription_cancelled_on", default)]
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp::option"))]
+    #[cfg_attr(
+        not(feature = "chrono"),
+        serde(deserialize_with = "optional::deserialize")
+    )]
+    pub cancelled_on: Option<Timestamp>,
     #[serde(rename = "subscription_created_on")]
-    pub created_on: String,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub created_on: Timestamp,
     #[serde(rename = "subscription_updated_on")]
-    pub updated_on: String,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub updated_on: Timestamp,
     #[serde(rename = "subscription_current_period_start")]
-    pub current_period_start: String,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub current_period_start: Timestamp,
     #[serde(rename = "subscription_current_period_end")]
-    pub current_period_end: String,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub current_period_end: Timestamp,
     #[serde(rename = "subscription_coffee_price")]
-    pub coffee_price: String,
+    pub coffee_price: CoffeePrice,
     #[serde(rename = "subscription_coffee_num")]
-    pub coffee_num: u16,
-    #[serde(rename = "subscription_is_cancelled", default)]
+    pub coffee_num: u32,
+    #[serde(
+        rename = "subscription_is_cancelled",
+        default,
+        deserialize_with = "flag::deserialize"
+    )]
     pub is_cancelled: bool,
-    #[serde(rename = "subscription_is_cancelled_at_period_end", default)]
+    #[serde(
+        rename = "subscription_is_cancelled_at_period_end",
+        default,
+        deserialize_with = "flag::deserialize"
+    )]
     pub is_cancelled_at_period_end: bool,
     #[serde(rename = "subscription_currency")]
-    pub currency: String,
-    #[serde(rename = "subscription_message")]
+    pub currency: Currency,
+    #[serde(
+        rename = "subscription_message",
+        default,
+        deserialize_with = "optional::deserialize"
+    )]
     pub message: Option<String>,
-    pub message_visibility: u8,
+    pub message_visibility: Visibility,
     #[serde(rename = "subscription_duration_type")]
-    pub duration_type: String,
+    pub duration_type: DurationType,
+    #[serde(default, deserialize_with = "optional::deserialize")]
     pub referer: Option<String>,
-    pub country: Option<String>,
-    pub transaction_id: String,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub country: Option<Country>,
+    pub transaction_id: TransactionId,
     pub payer_email: String,
     pub payer_name: String,
+    /// Fields sent by the API that this crate doesn't know about yet, so they
+    /// can still be accessed.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+/// How often a membership renews.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum DurationType {
+    Month,
+    Year,
+    /// A duration the API returned that this crate doesn't know about yet.
+    Other(String),
+}
+
+impl DurationType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DurationType::Month => "month",
+            DurationType::Year => "year",
+            DurationType::Other(other) => other,
+        }
+    }
+}
+
+impl From<String> for DurationType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "month" => DurationType::Month,
+            "year" => DurationType::Year,
+            _ => DurationType::Other(value),
+        }
+    }
+}
+
+impl From<DurationType> for String {
+    fn from(value: DurationType) -> Self {
+        match value {
+            DurationType::Other(other) => other,
+            _ => value.as_str().to_string(),
+        }
+    }
+}
+
+impl FromStr for DurationType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(s.to_string().into())
+    }
+}
+
+impl Display for DurationType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Membership {
+    /// Returns [`Membership::message`] without leading and trailing
+    /// whitespace, or [`None`] if it's missing, empty, or only whitespace.
+    ///
+    /// The API sometimes sends an empty message rather than none at all.
+    pub fn trimmed_message(&self) -> Option<&str> {
+        self.message
+            .as_deref()
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+    }
+
+    /// Formats this membership like [`Debug`], but with personal information
+    /// replaced by `[redacted]`, e.g. for sharing in a bug report.
+    ///
+    /// [`Membership::payer_email`], [`Membership::payer_name`], and
+    /// [`Membership::message`] are redacted, as well as every value of
+    /// [`Membership::extra_fields`]. IDs, amounts, dates, and everything else
+    /// are kept as they are.
+    pub fn redacted_debug(&self) -> String {
+        let mut membership = self.clone();
+
+        membership.payer_email = REDACTED.to_string();
+        membership.payer_name = REDACTED.to_string();
+        membership.message = membership.message.map(|_| REDACTED.to_string());
+        redact_extra_fields(&mut membership.extra_fields);
+
+        format!("{membership:?}")
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl Membership {
+    /// Returns the price of a single coffee multiplied by the number of
+    /// coffees, or [`None`] if the price couldn't be parsed.
+    pub fn total_price(&self) -> Option<Price> {
+        Some(self.coffee_price.value()? * Price::from(self.coffee_num))
+    }
+
+    /// Describes the price, e.g. `3 × $5.00 USD = $15.00`, for showing to
+    /// people, or returns [`None`] if the price couldn't be parsed.
+    ///
+    /// Common currencies are shown with their symbol, others only by code.
+    pub fn amount_display(&self) -> Option<String> {
+        Some(price::amount_display(
+            self.coffee_num,
+            self.coffee_price.value()?,
+            self.currency.as_str(),
+        ))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Membership {
+    /// Returns whether the membership currently grants access.
+    ///
+    /// A membership is active until its current period ends, unless it was
+    /// cancelled immediately rather than at the end of the period. The API's
+    /// timestamps carry no timezone and are assumed to be in UTC.
+    pub fn is_active(&self) -> bool {
+        if self.is_cancelled && !self.is_cancelled_at_period_end {
+            return false;
+        }
+
+        !self.is_expired()
+    }
+
+    /// Returns whether the current period has ended, i.e.
+    /// [`Membership::current_period_end`] has passed.
+    ///
+    /// Cancelling at the end of the period doesn't make a membership expire
+    /// any sooner, but this doesn't account for memberships cancelled
+    /// immediately, see [`Membership::is_active`] for that.
+    pub fn is_expired(&self) -> bool {
+        self.current_period_end <= chrono::Utc::now()
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[cfg(feature = "url")]
+impl Membership {
+    /// Returns [`Membership::referer`] parsed as a URL, or [`None`] if it's
+    /// missing, relative, or malformed.
+    pub fn referer_url(&self) -> Option<url::Url> {
+        referer::url(self.referer.as_deref())
+    }
+
+    /// Returns the host of [`Membership::referer_url`] without any leading
+    /// `www.`, e.g. `youtube.com`, for attributing members to where they came
+    /// from.
+    pub fn referer_host(&self) -> Option<String> {
+        referer::host(self.referer.as_deref())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Support {
     #[serde(rename = "support_id")]
     pub id: u32,
-    #[serde(rename = "support_note")]
+    #[serde(
+        rename = "support_note",
+        default,
+        deserialize_with = "optional::deserialize"
+    )]
     pub note: Option<String>,
     #[serde(rename = "support_coffees")]
-    pub coffee_num: u16,
-    pub transaction_id: String,
+    pub coffee_num: u32,
+    pub transaction_id: TransactionId,
     #[serde(rename = "support_visibility")]
-    pub visibility: u8,
+    pub visibility: Visibility,
     #[serde(rename = "support_created_on")]
-    pub created_on: String,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub created_on: Timestamp,
     #[serde(rename = "support_updated_on")]
-    pub updated_on: String,
-    pub transfer_id: Option<String>,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub updated_on: Timestamp,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub transfer_id: Option<TransferId>,
+    #[serde(default, deserialize_with = "optional::deserialize")]
     pub supporter_name: Option<String>,
     #[serde(rename = "support_coffee_price")]
-    pub coffee_price: String,
+    pub coffee_price: CoffeePrice,
     #[serde(rename = "support_email")]
     pub email: String,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "flag::deserialize")]
     pub is_refunded: bool,
     #[serde(rename = "support_currency")]
-    pub currency: String,
-    #[serde(rename = "support_note_pinned")]
-    pub note_pinned: u8,
+    pub currency: Currency,
+    #[serde(rename = "support_note_pinned", with = "flag")]
+    pub note_pinned: bool,
+    #[serde(default, deserialize_with = "optional::deserialize")]
     pub referer: Option<String>,
-    pub country: Option<String>,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub country: Option<Country>,
     pub payer_email: String,
     pub payment_platform: String,
     pub payer_name: String,
+    /// Fields sent by the API that this crate doesn't know about yet, so they
+    /// can still be accessed.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+impl Support {
+    /// Returns whether the note should be shown publicly, e.g. on a creator's
+    /// website.
+    ///
+    /// That's the case only if [`Support::visibility`] is
+    /// [`Visibility::Public`] and [`Support::note`] isn't missing, empty, or
+    /// only whitespace. Unknown visibilities are treated as private.
+    pub fn is_note_public(&self) -> bool {
+        self.visibility == Visibility::Public
+            && self
+                .note
+                .as_deref()
+                .is_some_and(|note| !note.trim().is_empty())
+    }
+
+    /// Formats this support like [`Debug`], but with personal information
+    /// replaced by `[redacted]`, e.g. for sharing in a bug report.
+    ///
+    /// [`Support::email`], [`Support::payer_email`], [`Support::payer_name`],
+    /// [`Support::supporter_name`], and [`Support::note`] are redacted, as
+    /// well as every value of [`Support::extra_fields`]. IDs, amounts, dates,
+    /// and everything else are kept as they are.
+    pub fn redacted_debug(&self) -> String {
+        let mut support = self.clone();
+
+        support.email = REDACTED.to_string();
+        support.payer_email = REDACTED.to_string();
+        support.payer_name = REDACTED.to_string();
+        support.supporter_name = support.supporter_name.map(|_| REDACTED.to_string());
+        support.note = support.note.map(|_| REDACTED.to_string());
+        redact_extra_fields(&mut support.extra_fields);
+
+        format!("{support:?}")
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl Support {
+    /// Returns the price of a single coffee multiplied by the number of
+    /// coffees, or [`None`] if the price couldn't be parsed.
+    pub fn total_price(&self) -> Option<Price> {
+        Some(self.coffee_price.value()? * Price::from(self.coffee_num))
+    }
+
+    /// Describes the price, e.g. `3 × $5.00 USD = $15.00`, for showing to
+    /// people, or returns [`None`] if the price couldn't be parsed.
+    ///
+    /// Common currencies are shown with their symbol, others only by code.
+    pub fn amount_display(&self) -> Option<String> {
+        Some(price::amount_display(
+            self.coffee_num,
+            self.coffee_price.value()?,
+            self.currency.as_str(),
+        ))
+    }
+}
+
+#[cfg(feature = "url")]
+impl Support {
+    /// Returns [`Support::referer`] parsed as a URL, or [`None`] if it's
+    /// missing, relative, or malformed.
+    pub fn referer_url(&self) -> Option<url::Url> {
+        referer::url(self.referer.as_deref())
+    }
+
+    /// Returns the host of [`Support::referer_url`] without any leading
+    /// `www.`, e.g. `twitter.com`, for attributing supporters to where they
+    /// came from.
+    pub fn referer_host(&self) -> Option<String> {
+        referer::host(self.referer.as_deref())
+    }
+}
+
+/// Who a message or note can be seen by.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8", into = "u8")]
+pub enum Visibility {
+    /// Only the creator can see it. Sent by the API as `0`.
+    Private,
+    /// Anybody can see it. Sent by the API as `1`.
+    Public,
+    /// A code the API returned that this crate doesn't know about yet.
+    Unknown(u8),
+}
+
+impl From<u8> for Visibility {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Visibility::Private,
+            1 => Visibility::Public,
+            _ => Visibility::Unknown(value),
+        }
+    }
+}
+
+impl From<Visibility> for u8 {
+    fn from(value: Visibility) -> Self {
+        match value {
+            Visibility::Private => 0,
+            Visibility::Public => 1,
+            Visibility::Unknown(value) => value,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Purchase {
     #[serde(rename = "purchase_id")]
     pub id: u32,
     #[serde(rename = "purchased_on")]
-    pub created_on: String,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub created_on: Timestamp,
     #[serde(rename = "purchase_updated_on")]
-    pub updated_on: String,
-    #[serde(rename = "purchase_is_revoked")]
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub updated_on: Timestamp,
+    #[serde(rename = "purchase_is_revoked", deserialize_with = "flag::deserialize")]
     pub is_revoked: bool,
     #[serde(rename = "purchase_amount")]
-    pub amount: String,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "price"))]
+    pub amount: Price,
     #[serde(rename = "purchase_currency")]
-    pub currency: String,
+    pub currency: Currency,
     #[serde(rename = "purchase_question")]
     pub question: String,
     pub payer_email: String,
     pub payer_name: String,
     pub extra: Extra,
+    /// Fields sent by the API that this crate doesn't know about yet, so they
+    /// can still be accessed.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+impl Purchase {
+    /// Formats this purchase like [`Debug`], but with personal information
+    /// replaced by `[redacted]`, e.g. for sharing in a bug report.
+    ///
+    /// [`Purchase::payer_email`] and [`Purchase::payer_name`] are redacted, as
+    /// well as every value of [`Purchase::extra_fields`] and of the extra's
+    /// [`Extra::extra_fields`].
+    /// IDs, amounts, dates, and everything else, including the rest of
+    /// [`Purchase::extra`], are kept as they are.
+    pub fn redacted_debug(&self) -> String {
+        let mut purchase = self.clone();
+
+        purchase.payer_email = REDACTED.to_string();
+        purchase.payer_name = REDACTED.to_string();
+        redact_extra_fields(&mut purchase.extra_fields);
+        redact_extra_fields(&mut purchase.extra.extra_fields);
+
+        format!("{purchase:?}")
+    }
+
+    /// Returns the main fields of this purchase and of its [`Purchase::extra`]
+    /// side by side, e.g. for writing rows of a spreadsheet.
+    ///
+    /// See [`FlatPurchase`] for the fields included.
+    // Timestamps and prices are only `Copy` with the `chrono` and
+    // `rust_decimal` features
+    #[cfg_attr(
+        any(feature = "chrono", feature = "rust_decimal"),
+        allow(clippy::clone_on_copy)
+    )]
+    pub fn to_flat_record(&self) -> FlatPurchase {
+        FlatPurchase {
+            id: self.id,
+            created_on: self.created_on.clone(),
+            is_revoked: self.is_revoked,
+            amount: self.amount.clone(),
+            currency: self.currency.clone(),
+            question: self.question.clone(),
+            payer_email: self.payer_email.clone(),
+            payer_name: self.payer_name.clone(),
+            extra_id: self.extra.id,
+            extra_title: self.extra.title.clone(),
+            extra_coffee_price: self.extra.coffee_price.clone(),
+        }
+    }
+}
+
+/// A [`Purchase`] without nesting, as returned by [`Purchase::to_flat_record`].
+///
+/// Includes the purchase's ID, creation time, revocation, amount, currency,
+/// question, and payer, followed by the ID, title, and coffee price of its
+/// extra. Serializes with the same formats as [`Purchase`], so it can be
+/// written as a row of a CSV file as is.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Hash)]
+pub struct FlatPurchase {
+    pub id: u32,
+    #[cfg_attr(feature = "chrono", serde(serialize_with = "timestamp::serialize"))]
+    pub created_on: Timestamp,
+    pub is_revoked: bool,
+    #[cfg_attr(feature = "rust_decimal", serde(serialize_with = "price::serialize"))]
+    pub amount: Price,
+    pub currency: Currency,
+    pub question: String,
+    pub payer_email: String,
+    pub payer_name: String,
+    pub extra_id: u32,
+    pub extra_title: String,
+    pub extra_coffee_price: CoffeePrice,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Extra {
     #[serde(rename = "reward_id")]
     pub id: u32,
@@ -323,21 +2716,44 @@ pub struct Extra {
     #[serde(rename = "reward_question")]
     pub question: String,
     #[serde(rename = "reward_used")]
-    pub used: u8,
+    pub used: u32,
     #[serde(rename = "reward_created_on")]
-    pub created_on: String,
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub created_on: Timestamp,
     #[serde(rename = "reward_updated_on")]
-    pub updated_on: String,
-    #[serde(rename = "reward_deleted_on")]
-    pub deleted_on: Option<String>,
-    #[serde(rename = "reward_is_active")]
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp"))]
+    pub updated_on: Timestamp,
+    #[serde(rename = "reward_deleted_on", default)]
+    #[cfg_attr(feature = "chrono", serde(with = "timestamp::option"))]
+    #[cfg_attr(
+        not(feature = "chrono"),
+        serde(deserialize_with = "optional::deserialize")
+    )]
+    pub deleted_on: Option<Timestamp>,
+    #[serde(rename = "reward_is_active", deserialize_with = "flag::deserialize")]
     pub is_active: bool,
     #[serde(rename = "reward_image")]
-    pub image: String,
+    pub image: Image,
     #[serde(rename = "reward_slots")]
-    pub slots: u8,
+    pub slots: u32,
     #[serde(rename = "reward_coffee_price")]
-    pub coffee_price: String,
+    pub coffee_price: CoffeePrice,
     #[serde(rename = "reward_order")]
     pub order: u8,
+    /// Fields sent by the API that this crate doesn't know about yet, so they
+    /// can still be accessed.
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
+impl Extra {
+    /// Returns how many more times this extra can be purchased, or [`None`]
+    /// if there's no limit.
+    ///
+    /// The API sends [`Extra::slots`] as `0` for extras without a limit. Once
+    /// every slot has been used, this returns `Some(0)`, even if the API
+    /// reports more used slots than there are.
+    pub fn remaining_slots(&self) -> Option<u32> {
+        (self.slots != 0).then(|| self.slots.saturating_sub(self.used))
+    }
 }
@@ -0,0 +1,17 @@
+//! Deserialization of the optional values sent by the API inconsistently, as
+//! `null`, an empty string, or not at all depending on the record.
+
+use serde::{Deserialize, Deserializer};
+
+/// Parses a string, treating `null` and an empty or whitespace-only string as
+/// [`None`]. Fields using this should also use `#[serde(default)]`, so that a
+/// missing field is [`None`] too.
+pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<String>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?
+        .filter(|value| !value.trim().is_empty())
+        .map(T::from))
+}
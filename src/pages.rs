@@ -0,0 +1,216 @@
+//! Cursors for walking through pages by hand.
+
+use std::{collections::HashMap, future::Future, sync::Mutex};
+
+use crate::{
+    Client, Error, MemberStatus, Membership, Page, Purchase, Result, ServerErrorKind, Support,
+};
+
+/// The last page of each listing seen so far, shared between clones of a
+/// [`Client`], to tell when a page past it is requested.
+#[derive(Debug, Default)]
+pub(crate) struct LastPages(Mutex<HashMap<String, u16>>);
+
+impl LastPages {
+    pub(crate) fn get(&self, listing: &str) -> Option<u16> {
+        self.0
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get(listing)
+            .copied()
+    }
+
+    pub(crate) fn insert(&self, listing: String, last_page: u16) {
+        self.0
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(listing, last_page);
+    }
+
+    /// Replaces the `empty` sentinel error, or a response without a body,
+    /// with an empty page, or with [`Error::PageOutOfRange`] if `page` is
+    /// past the last page of `listing` seen before. Remembers the last page
+    /// of successful responses.
+    pub(crate) fn check<T>(
+        &self,
+        listing: String,
+        result: Result<Page<T>>,
+        empty: ServerErrorKind,
+        page: u16,
+    ) -> Result<Page<T>> {
+        match result {
+            Ok(result) => {
+                self.insert(listing, result.last_page);
+
+                Ok(result)
+            }
+            Err(Error::Server(err)) if err.kind() == empty => self.empty_page(&listing, page),
+            Err(Error::EmptyResponse(_)) => self.empty_page(&listing, page),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns [`Error::PageOutOfRange`] if `page` is past the last page of
+    /// `listing` seen before, or an empty page otherwise.
+    fn empty_page<T>(&self, listing: &str, page: u16) -> Result<Page<T>> {
+        match self.get(listing) {
+            Some(last_page) if page > last_page => Err(Error::PageOutOfRange {
+                requested: page,
+                last_page,
+            }),
+            _ => Ok(Page::empty(page)),
+        }
+    }
+}
+
+/// Records where a cursor should continue after `page`, returning the page
+/// unless it's empty.
+fn advance<T>(next: &mut Option<u16>, page: Page<T>) -> Option<Page<T>> {
+    if page.is_empty() {
+        *next = None;
+
+        return None;
+    }
+
+    *next = page.has_next().then(|| page.current_page + 1);
+
+    Some(page)
+}
+
+impl Page<Membership> {
+    /// Returns a future fetching the page after this one with `client`, or
+    /// [`None`] if this is the last page.
+    ///
+    /// Pages don't know which [`MemberStatus`] they were listed with, so it
+    /// has to be given again. The future only borrows `client`, not this
+    /// page, so the page can be dropped or moved before awaiting it.
+    ///
+    /// ```no_run
+    /// # async fn example(client: buy_me_a_coffee::Client) -> buy_me_a_coffee::Result<()> {
+    /// use buy_me_a_coffee::MemberStatus;
+    ///
+    /// let mut page = client.members(MemberStatus::Active, 1).await?;
+    ///
+    /// loop {
+    ///     for membership in &page.data {
+    ///         println!("{}", membership.payer_name);
+    ///     }
+    ///
+    ///     match page.next(&client, MemberStatus::Active) {
+    ///         Some(next) => page = next.await?,
+    ///         None => break,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn next<'a>(
+        &self,
+        client: &'a Client,
+        status: MemberStatus,
+    ) -> Option<impl Future<Output = Result<Page<Membership>>> + 'a> {
+        let page = self.has_next().then(|| self.current_page + 1)?;
+
+        Some(client.members(status, page))
+    }
+}
+
+impl Page<Support> {
+    /// Returns a future fetching the page after this one with `client`, or
+    /// [`None`] if this is the last page. See [`Page::<Membership>::next`]
+    /// for an example.
+    pub fn next<'a>(
+        &self,
+        client: &'a Client,
+    ) -> Option<impl Future<Output = Result<Page<Support>>> + 'a> {
+        let page = self.has_next().then(|| self.current_page + 1)?;
+
+        Some(client.supporters(page))
+    }
+}
+
+impl Page<Purchase> {
+    /// Returns a future fetching the page after this one with `client`, or
+    /// [`None`] if this is the last page. See [`Page::<Membership>::next`]
+    /// for an example.
+    pub fn next<'a>(
+        &self,
+        client: &'a Client,
+    ) -> Option<impl Future<Output = Result<Page<Purchase>>> + 'a> {
+        let page = self.has_next().then(|| self.current_page + 1)?;
+
+        Some(client.extras(page))
+    }
+}
+
+/// A cursor over pages of members, returned by [`Client::member_pages`].
+#[derive(Debug, Clone)]
+pub struct MemberPages<'a> {
+    pub(crate) client: &'a Client,
+    pub(crate) status: MemberStatus,
+    pub(crate) next: Option<u16>,
+}
+
+impl MemberPages<'_> {
+    /// Fetches the next page, or returns [`None`] once the last page has been
+    /// returned.
+    ///
+    /// If fetching fails, the cursor doesn't advance, so calling this again
+    /// retries the same page.
+    pub async fn next_page(&mut self) -> Result<Option<Page<Membership>>> {
+        let Some(page) = self.next else {
+            return Ok(None);
+        };
+        let page = self.client.members(self.status, page).await?;
+
+        Ok(advance(&mut self.next, page))
+    }
+}
+
+/// A cursor over pages of onetime-supporters, returned by
+/// [`Client::supporter_pages`].
+#[derive(Debug, Clone)]
+pub struct SupporterPages<'a> {
+    pub(crate) client: &'a Client,
+    pub(crate) next: Option<u16>,
+}
+
+impl SupporterPages<'_> {
+    /// Fetches the next page, or returns [`None`] once the last page has been
+    /// returned.
+    ///
+    /// If fetching fails, the cursor doesn't advance, so calling this again
+    /// retries the same page.
+    pub async fn next_page(&mut self) -> Result<Option<Page<Support>>> {
+        let Some(page) = self.next else {
+            return Ok(None);
+        };
+        let page = self.client.supporters(page).await?;
+
+        Ok(advance(&mut self.next, page))
+    }
+}
+
+/// A cursor over pages of extra purchases, returned by
+/// [`Client::extra_pages`].
+#[derive(Debug, Clone)]
+pub struct ExtraPages<'a> {
+    pub(crate) client: &'a Client,
+    pub(crate) next: Option<u16>,
+}
+
+impl ExtraPages<'_> {
+    /// Fetches the next page, or returns [`None`] once the last page has been
+    /// returned.
+    ///
+    /// If fetching fails, the cursor doesn't advance, so calling this again
+    /// retries the same page.
+    pub async fn next_page(&mut self) -> Result<Option<Page<Purchase>>> {
+        let Some(page) = self.next else {
+            return Ok(None);
+        };
+        let page = self.client.extras(page).await?;
+
+        Ok(advance(&mut self.next, page))
+    }
+}
@@ -0,0 +1,127 @@
+//! (De)serialization of the prices returned by the API as [`Decimal`]s, and
+//! their formatting.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+pub(crate) fn serialize<S: Serializer>(price: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(price)
+}
+
+/// Parses a price, treating an empty string as [`Decimal::ZERO`].
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+    parse(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+fn parse(price: &str) -> Result<Decimal, rust_decimal::Error> {
+    let price = price.trim();
+
+    if price.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+
+    Decimal::from_str(price)
+}
+
+/// The price of a single coffee as sent by the API.
+///
+/// A price that can't be parsed doesn't fail deserialization, so that one
+/// malformed record can't break a whole page. Its [`CoffeePrice::value`] is
+/// [`None`] instead, while [`CoffeePrice::raw`] still returns it as sent.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub struct CoffeePrice {
+    raw: String,
+    value: Option<Decimal>,
+}
+
+impl CoffeePrice {
+    /// Returns the parsed price, or [`None`] if it isn't a valid number. An
+    /// empty string is parsed as [`Decimal::ZERO`].
+    pub fn value(&self) -> Option<Decimal> {
+        self.value
+    }
+
+    /// Returns the price exactly as sent by the API, e.g. `5.0000`.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl From<String> for CoffeePrice {
+    fn from(value: String) -> Self {
+        Self {
+            value: parse(&value).ok(),
+            raw: value,
+        }
+    }
+}
+
+impl From<CoffeePrice> for String {
+    fn from(value: CoffeePrice) -> Self {
+        value.raw
+    }
+}
+
+impl From<Decimal> for CoffeePrice {
+    fn from(value: Decimal) -> Self {
+        Self {
+            raw: value.to_string(),
+            value: Some(value),
+        }
+    }
+}
+
+impl Display for CoffeePrice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+/// Symbols and decimal places of commonly used currencies, by code.
+const CURRENCIES: &[(&str, &str, u32)] = &[
+    ("AUD", "$", 2),
+    ("BRL", "R$", 2),
+    ("CAD", "$", 2),
+    ("EUR", "€", 2),
+    ("GBP", "£", 2),
+    ("INR", "₹", 2),
+    ("JPY", "¥", 0),
+    ("KRW", "₩", 0),
+    ("MXN", "$", 2),
+    ("NZD", "$", 2),
+    ("USD", "$", 2),
+];
+
+/// Formats `coffee_num` coffees of `coffee_price` each, e.g.
+/// `3 × $5.00 USD = $15.00`.
+///
+/// Currencies without a known symbol are written out by code instead, e.g.
+/// `3 × 5.00 SEK = 15.00 SEK`.
+pub(crate) fn amount_display(coffee_num: u32, coffee_price: Decimal, currency: &str) -> String {
+    let known = CURRENCIES
+        .iter()
+        .find(|(code, _, _)| code.eq_ignore_ascii_case(currency));
+    let decimals = known.map_or(2, |&(_, _, decimals)| decimals);
+    let format = |amount: Decimal| {
+        format!(
+            "{:.*}",
+            decimals as usize,
+            amount.round_dp_with_strategy(decimals, RoundingStrategy::MidpointAwayFromZero),
+        )
+    };
+    let price = format(coffee_price);
+    let total = format(coffee_price * Decimal::from(coffee_num));
+
+    match known {
+        Some((_, symbol, _)) => {
+            format!("{coffee_num} × {symbol}{price} {currency} = {symbol}{total}")
+        }
+        None => format!("{coffee_num} × {price} {currency} = {total} {currency}"),
+    }
+}
@@ -0,0 +1,36 @@
+//! Abstracting over where memberships come from.
+
+use async_trait::async_trait;
+
+use crate::{Client, MemberStatus, Membership, Page, Result};
+
+/// Something memberships can be listed and looked up from, like a [`Client`].
+///
+/// Code that only needs memberships can be generic over this trait, so that a
+/// mock or another provider can be used in place of the API, e.g. in tests.
+/// Implementations are written with [`async_trait`], which must be applied to
+/// them too. On WebAssembly, the futures returned aren't [`Send`].
+///
+/// [`async_trait`]: https://docs.rs/async-trait
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait SubscriptionProvider {
+    /// Returns a page of memberships, like [`Client::members`].
+    async fn members(&self, status: MemberStatus, page: u16) -> Result<Page<Membership>>;
+
+    /// Returns the membership with the ID provided, like
+    /// [`Client::membership`].
+    async fn membership(&self, id: u32) -> Result<Membership>;
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl SubscriptionProvider for Client {
+    async fn members(&self, status: MemberStatus, page: u16) -> Result<Page<Membership>> {
+        Client::members(self, status, page).await
+    }
+
+    async fn membership(&self, id: u32) -> Result<Membership> {
+        Client::membership(self, id).await
+    }
+}
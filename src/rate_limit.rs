@@ -0,0 +1,49 @@
+//! Throttling of requests so that they stay under a rate limit, shared between
+//! clones of a client.
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// A token bucket holding up to a second's worth of requests, refilled
+/// continuously.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    /// How often a request is let through once the bucket is empty.
+    interval: Duration,
+    /// How far ahead of the schedule requests may be let through, i.e. how
+    /// big a burst is allowed.
+    burst: Duration,
+    /// When the next request would be let through if there were no bursts.
+    next: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    /// # Panics
+    ///
+    /// Panics if `requests_per_second` is zero.
+    pub(crate) fn new(requests_per_second: u32) -> Self {
+        assert!(requests_per_second > 0, "rate limit should not be zero");
+
+        let interval = Duration::from_secs(1) / requests_per_second;
+
+        Self {
+            interval,
+            burst: interval * (requests_per_second - 1),
+            next: Mutex::new(None),
+        }
+    }
+
+    /// Reserves a slot for a request, returning how long to wait before
+    /// sending it.
+    pub(crate) fn reserve(&self) -> Duration {
+        let now = Instant::now();
+        let mut next = self.next.lock().unwrap_or_else(|err| err.into_inner());
+        let scheduled = next.map_or(now, |next| next.max(now));
+
+        *next = Some(scheduled + self.interval);
+
+        (scheduled - now).saturating_sub(self.burst)
+    }
+}
@@ -0,0 +1,19 @@
+//! Parsing the pages that members and supporters came from.
+
+use url::Url;
+
+/// Parses `referer` as an absolute URL with a host, or returns [`None`] if it
+/// is missing, relative, or malformed.
+pub(crate) fn url(referer: Option<&str>) -> Option<Url> {
+    Url::parse(referer?.trim())
+        .ok()
+        .filter(|url| url.host_str().is_some_and(|host| !host.is_empty()))
+}
+
+/// Returns the host of `referer` without any leading `www.`.
+pub(crate) fn host(referer: Option<&str>) -> Option<String> {
+    let url = url(referer)?;
+    let host = url.host_str()?;
+
+    Some(host.strip_prefix("www.").unwrap_or(host).to_string())
+}
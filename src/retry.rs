@@ -0,0 +1,161 @@
+//! Retrying of requests that failed for reasons that are likely transient.
+
+use std::time::{Duration, SystemTime};
+
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+
+/// How requests that failed transiently should be retried.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Retry {
+    pub(crate) max_retries: u32,
+    pub(crate) base_delay: Duration,
+    pub(crate) max_wait: Duration,
+}
+
+impl Retry {
+    /// Returns how long to wait before the retry following `attempt` failed
+    /// attempts, having already `waited` in total, or [`None`] if no more
+    /// retries should be made.
+    ///
+    /// Without a `retry_after` from the server, the delay doubles with every
+    /// attempt and is jittered down by up to half to avoid retrying in
+    /// lockstep with other clients.
+    pub(crate) fn delay(
+        &self,
+        attempt: u32,
+        waited: Duration,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+
+        let delay = retry_after.unwrap_or_else(|| {
+            self.base_delay
+                .saturating_mul(2u32.saturating_pow(attempt))
+                .mul_f64(0.5 + fastrand::f64() / 2.0)
+        });
+
+        (waited + delay <= self.max_wait).then_some(delay)
+    }
+}
+
+/// The retries made of a single request so far, for deciding whether to make
+/// another the same way in the asynchronous and the blocking client.
+#[derive(Debug, Default)]
+pub(crate) struct Backoff {
+    attempt: u32,
+    waited: Duration,
+}
+
+impl Backoff {
+    /// Returns how long to wait before retrying a request that resulted in
+    /// `result`, see [`should_retry`], and counts the retry, or returns
+    /// [`None`] if it shouldn't be retried with `retry`.
+    pub(crate) fn next(
+        &mut self,
+        retry: Option<Retry>,
+        result: Result<(StatusCode, &HeaderMap), &reqwest::Error>,
+    ) -> Option<Duration> {
+        let delay = retry?.delay(self.attempt, self.waited, should_retry(result)?)?;
+
+        self.attempt += 1;
+        self.waited += delay;
+
+        Some(delay)
+    }
+}
+
+/// How many times requests were retried, and how long it took, as returned by
+/// [`Client::with_attempts`].
+///
+/// [`Client::with_attempts`]: crate::Client::with_attempts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Attempts {
+    /// How many retries were made, across every request.
+    pub retries: u32,
+    /// How long it took in total, including waiting between retries.
+    pub elapsed: Duration,
+}
+
+/// Returns whether a request that resulted in `result`, either a response's
+/// status and headers or an error, is worth retrying, and the delay the server
+/// asked for, if any.
+pub(crate) fn should_retry(
+    result: Result<(StatusCode, &HeaderMap), &reqwest::Error>,
+) -> Option<Option<Duration>> {
+    match result {
+        Ok((status, headers)) if status.is_server_error() => Some(retry_after(headers)),
+        Ok(_) => None,
+        Err(err) => (is_connect(err) || err.is_timeout() || err.is_request()).then_some(None),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connect(err: &reqwest::Error) -> bool {
+    err.is_connect()
+}
+
+/// Browsers don't say whether a request failed to connect.
+#[cfg(target_arch = "wasm32")]
+fn is_connect(_err: &reqwest::Error) -> bool {
+    false
+}
+
+/// Parses the `Retry-After` header, if present, in either its delay-seconds
+/// or its HTTP-date form.
+///
+/// A date in the past results in a delay of zero.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let retry_after = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = retry_after.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(retry_after).ok()?;
+
+    Some(date.duration_since(now()).unwrap_or(Duration::ZERO))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now() -> SystemTime {
+    SystemTime::now()
+}
+
+/// [`SystemTime::now`] panics in browsers, so ask JavaScript instead.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn now() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(js_sys::Date::now() as u64)
+}
+
+/// Waits for `duration` before retrying.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Waits for `duration` before retrying, using JavaScript's `setTimeout`
+/// since there's no Tokio runtime in browsers.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(duration: Duration) {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("setTimeout"))
+            .expect("global scope should have setTimeout")
+            .unchecked_into::<js_sys::Function>()
+            .call2(
+                &JsValue::NULL,
+                &resolve,
+                &JsValue::from_f64(duration.as_millis() as f64),
+            )
+            .expect("setTimeout should not throw");
+    });
+
+    // The promise only ever resolves
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
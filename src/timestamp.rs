@@ -0,0 +1,53 @@
+//! (De)serialization of the timestamps returned by the API as [`DateTime`]s.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+/// The format the API uses for timestamps, e.g. `2024-01-02 15:04:05`.
+const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+fn parse(timestamp: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
+    NaiveDateTime::parse_from_str(timestamp, FORMAT)
+        .map(|naive| naive.and_utc())
+        .or_else(|_| DateTime::parse_from_rfc3339(timestamp).map(|date| date.to_utc()))
+}
+
+pub(crate) fn serialize<S: Serializer>(
+    timestamp: &DateTime<Utc>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&timestamp.format(FORMAT))
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DateTime<Utc>, D::Error> {
+    let timestamp = String::deserialize(deserializer)?;
+
+    parse(&timestamp).map_err(D::Error::custom)
+}
+
+/// Like the parent module, but for nullable timestamps.
+pub(crate) mod option {
+    use chrono::{DateTime, Utc};
+    use serde::{de::Error, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        timestamp: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match timestamp {
+            Some(timestamp) => super::serialize(timestamp, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Parses a timestamp, treating `null` and an empty string as [`None`].
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        crate::optional::deserialize::<_, String>(deserializer)?
+            .map(|timestamp| super::parse(&timestamp).map_err(D::Error::custom))
+            .transpose()
+    }
+}
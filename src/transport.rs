@@ -0,0 +1,169 @@
+//! Sending requests through something other than [`reqwest::Client`].
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Component, Path, PathBuf};
+
+#[cfg(target_arch = "wasm32")]
+use futures::future::LocalBoxFuture;
+#[cfg(not(target_arch = "wasm32"))]
+use futures::future::{ready, BoxFuture};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::{header::CONTENT_TYPE, StatusCode, Url};
+use reqwest::{Request, Response};
+
+/// The future returned by [`HttpTransport::get`].
+///
+/// On WebAssembly, where responses can't be sent to other threads, this isn't
+/// [`Send`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> = BoxFuture<'a, reqwest::Result<Response>>;
+/// The future returned by [`HttpTransport::get`].
+///
+/// On WebAssembly, where responses can't be sent to other threads, this isn't
+/// [`Send`].
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> = LocalBoxFuture<'a, reqwest::Result<Response>>;
+
+/// Sends the requests made by a [`Client`](crate::Client).
+///
+/// [`reqwest::Client`] implements this and is used by default. Providing
+/// another implementation with [`ClientBuilder::transport`] makes it possible
+/// to test code that uses a [`Client`](crate::Client) with canned responses,
+/// without a server. A [`Response`] can be created from an
+/// `http::Response<T>` with [`Response::from`].
+///
+/// Retrying, caching, and checking responses for errors all still happen
+/// around the transport, which only needs to send a single request.
+///
+/// [`ClientBuilder::transport`]: crate::ClientBuilder::transport
+pub trait HttpTransport: Send + Sync {
+    /// Sends a GET `request` and returns the response, whatever its status.
+    fn get(&self, request: Request) -> TransportFuture<'_>;
+}
+
+impl HttpTransport for reqwest::Client {
+    fn get(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(self.execute(request))
+    }
+}
+
+/// An [`HttpTransport`] that responds with JSON files from a directory instead
+/// of sending requests, for deterministic tests against recorded responses.
+///
+/// Each request is answered with the file at [`ReplayTransport::path`] for its
+/// URL, or with 404 Not Found if there is none. Files are named after the
+/// endpoint, without the base URL and `/v1/`, followed by each query parameter
+/// as `_key-value` in alphabetical order, and `.json`. For example:
+///
+/// - `/v1/supporters/245731` is read from `supporters/245731.json`.
+/// - `/v1/supporters?page=2` is read from `supporters_page-2.json`.
+/// - `/v1/subscriptions?status=active&page=1` is read from
+///   `subscriptions_page-1_status-active.json`.
+///
+/// Slashes and backslashes within a segment or query parameter, and segments
+/// that are empty or only `.` or `..`, are percent-encoded, so that no URL can
+/// point outside of the directory.
+///
+/// Real responses can be recorded by requesting them with
+/// [`Client::get_raw`] as a `serde_json::Value` and writing them to those
+/// paths. Not available on WebAssembly.
+///
+/// ```no_run
+/// use buy_me_a_coffee::{Client, ReplayTransport};
+///
+/// let client = Client::builder("unused token")
+///     .transport(ReplayTransport::new("tests/fixtures"))
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// [`Client::get_raw`]: crate::Client::get_raw
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct ReplayTransport {
+    dir: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ReplayTransport {
+    /// Creates a transport that reads responses from `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the path of the file that a request to `url` is answered with.
+    pub fn path(&self, url: &Url) -> PathBuf {
+        let segments: Vec<_> = url
+            .path_segments()
+            .map(Iterator::collect)
+            .unwrap_or_default();
+        let start = segments
+            .iter()
+            .position(|segment| *segment == "v1")
+            .map_or(0, |index| index + 1);
+        let mut query: Vec<_> = url.query_pairs().collect();
+        let mut segments: Vec<_> = segments[start..].iter().map(|s| escape(s)).collect();
+        let mut name = segments.pop().unwrap_or_default();
+        let mut path = PathBuf::new();
+
+        query.sort();
+
+        for (key, value) in query {
+            name.push_str(&escape(&format!("_{key}-{value}")));
+        }
+
+        for segment in segments {
+            path.push(segment);
+        }
+
+        path.push(name + ".json");
+
+        assert!(
+            is_relative(&path),
+            "fixture path {path:?} should stay within the directory",
+        );
+
+        self.dir.join(path)
+    }
+}
+
+/// Percent-encodes what would let `component` of a fixture's path name
+/// another directory: path separators, and being empty or only `.` or `..`.
+#[cfg(not(target_arch = "wasm32"))]
+fn escape(component: &str) -> String {
+    if component.is_empty() {
+        return "%00".to_string();
+    }
+
+    if component == "." || component == ".." {
+        return component.replace('.', "%2E");
+    }
+
+    component.replace('/', "%2F").replace('\\', "%5C")
+}
+
+/// Returns whether `path` only names entries within whatever it's joined to,
+/// without a root, a prefix like `C:`, or any `.` or `..`.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_relative(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HttpTransport for ReplayTransport {
+    fn get(&self, request: Request) -> TransportFuture<'_> {
+        let response = match std::fs::read(self.path(request.url())) {
+            Ok(body) => http::Response::builder()
+                .header(CONTENT_TYPE, "application/json")
+                .body(body),
+            Err(_) => http::Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Vec::new()),
+        };
+
+        Box::pin(ready(Ok(response
+            .expect("response should be valid")
+            .into())))
+    }
+}
@@ -0,0 +1,247 @@
+//! Parsing of the webhooks Buy Me a Coffee sends when something happens, as
+//! an alternative to polling the API.
+//!
+//! ```no_run
+//! use buy_me_a_coffee::webhook::{self, WebhookEvent};
+//!
+//! fn handle(body: &[u8]) -> buy_me_a_coffee::Result<()> {
+//!     match webhook::parse_webhook(body)? {
+//!         WebhookEvent::Support(event) => {
+//!             println!("{} bought a coffee", event.data.supporter_name);
+//!         }
+//!         WebhookEvent::Membership(event) if event.event_type == "membership.started" => {
+//!             println!("{} became a member", event.data.supporter_name);
+//!         }
+//!         _ => {}
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The payloads of webhooks aren't shaped like the API's responses, so they
+//! have their own types rather than reusing [`Support`](crate::Support),
+//! [`Membership`](crate::Membership), and [`Purchase`](crate::Purchase).
+//!
+//! Anybody can send a request to a webhook's URL, so check that it came from
+//! Buy Me a Coffee with `verify_signature` (requires the `ring` feature)
+//! before parsing it.
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{deserialize_error, optional, Currency, DurationType, Price, Result, TransactionId};
+
+/// A webhook, parsed by [`parse_webhook`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum WebhookEvent {
+    /// A `donation.*` event, e.g. `donation.created`, about a onetime support.
+    Support(Webhook<SupportData>),
+    /// A `membership.*` event, e.g. `membership.started` or
+    /// `membership.cancelled`.
+    Membership(Webhook<MembershipData>),
+    /// An `extra_purchase.*` event, e.g. `extra_purchase.created`.
+    Extra(Webhook<ExtraPurchaseData>),
+    /// An event of a type this crate doesn't know about yet, with its data as
+    /// it was sent.
+    Other(Webhook<serde_json::Value>),
+}
+
+/// The envelope every webhook's data is sent in.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Webhook<T> {
+    /// What happened, e.g. `donation.created`.
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// Whether this is a real event rather than a test sent from the
+    /// dashboard.
+    pub live_mode: bool,
+    /// How many times this webhook has been sent, starting from `1`.
+    pub attempt: u32,
+    /// When the webhook was sent, in seconds since the Unix epoch.
+    pub created: i64,
+    pub event_id: u64,
+    pub data: T,
+}
+
+/// The data of a `donation.*` webhook.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SupportData {
+    pub id: u64,
+    #[serde(deserialize_with = "price")]
+    pub amount: Price,
+    pub currency: Currency,
+    #[serde(default)]
+    pub coffee_count: u32,
+    pub status: String,
+    #[serde(default, deserialize_with = "crate::flag::deserialize")]
+    pub refunded: bool,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub support_note: Option<String>,
+    pub supporter_name: String,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub supporter_email: Option<String>,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub transaction_id: Option<TransactionId>,
+    /// In seconds since the Unix epoch.
+    pub created_at: i64,
+}
+
+/// The data of a `membership.*` webhook.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MembershipData {
+    pub id: u64,
+    #[serde(deserialize_with = "price")]
+    pub amount: Price,
+    pub currency: Currency,
+    pub status: String,
+    #[serde(default, deserialize_with = "crate::flag::deserialize")]
+    pub canceled: bool,
+    #[serde(default, deserialize_with = "crate::flag::deserialize")]
+    pub paused: bool,
+    pub duration_type: DurationType,
+    pub membership_level_id: u64,
+    pub membership_level_name: String,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub support_note: Option<String>,
+    pub supporter_name: String,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub supporter_email: Option<String>,
+    /// In seconds since the Unix epoch.
+    pub started_at: i64,
+    /// In seconds since the Unix epoch.
+    pub canceled_at: Option<i64>,
+    /// In seconds since the Unix epoch.
+    pub current_period_start: i64,
+    /// In seconds since the Unix epoch.
+    pub current_period_end: i64,
+}
+
+/// The data of an `extra_purchase.*` webhook.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ExtraPurchaseData {
+    pub id: u64,
+    #[serde(deserialize_with = "price")]
+    pub amount: Price,
+    pub currency: Currency,
+    pub status: String,
+    #[serde(default, deserialize_with = "crate::flag::deserialize")]
+    pub refunded: bool,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub support_note: Option<String>,
+    pub supporter_name: String,
+    #[serde(default, deserialize_with = "optional::deserialize")]
+    pub supporter_email: Option<String>,
+    /// In seconds since the Unix epoch.
+    pub created_at: i64,
+    /// The extras that were purchased.
+    #[serde(default)]
+    pub extras: Vec<ExtraItem>,
+}
+
+/// An extra purchased, as part of an [`ExtraPurchaseData`].
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ExtraItem {
+    pub id: u64,
+    pub title: String,
+    #[serde(deserialize_with = "price")]
+    pub amount: Price,
+    #[serde(default = "one")]
+    pub quantity: u32,
+}
+
+/// Parses the `body` of a webhook request.
+///
+/// Fails with [`Error::Deserialize`](crate::Error::Deserialize) if the body
+/// isn't a webhook, or if it's a webhook of a known type whose data isn't
+/// shaped as expected.
+pub fn parse_webhook(body: &[u8]) -> Result<WebhookEvent> {
+    let webhook: Webhook<serde_json::Value> =
+        serde_json::from_slice(body).map_err(|source| deserialize_error(source, body))?;
+    let (kind, _) = webhook
+        .event_type
+        .split_once('.')
+        .unwrap_or((&webhook.event_type, ""));
+
+    match kind {
+        "donation" => with_data(webhook, body).map(WebhookEvent::Support),
+        "membership" => with_data(webhook, body).map(WebhookEvent::Membership),
+        "extra_purchase" => with_data(webhook, body).map(WebhookEvent::Extra),
+        _ => Ok(WebhookEvent::Other(webhook)),
+    }
+}
+
+/// Parses the data of `webhook` into `T`.
+fn with_data<T: for<'de> Deserialize<'de>>(
+    webhook: Webhook<serde_json::Value>,
+    body: &[u8],
+) -> Result<Webhook<T>> {
+    Ok(Webhook {
+        data: serde_json::from_value(webhook.data)
+            .map_err(|source| deserialize_error(source, body))?,
+        event_type: webhook.event_type,
+        live_mode: webhook.live_mode,
+        attempt: webhook.attempt,
+        created: webhook.created,
+        event_id: webhook.event_id,
+    })
+}
+
+/// The header Buy Me a Coffee sends a webhook's signature in.
+#[cfg(feature = "ring")]
+pub const SIGNATURE_HEADER: &str = "x-signature-sha256";
+
+/// Returns whether `signature_header`, the value of the [`SIGNATURE_HEADER`]
+/// header, is a valid signature of `body` made with the webhook's `secret`.
+///
+/// The signature is the HMAC-SHA256 of the raw request body, keyed with the
+/// secret shown when creating the webhook, encoded as hexadecimal. `body`
+/// must be exactly as it was received, since parsing and re-serializing it
+/// would change the signature. Signatures are compared in constant time.
+#[cfg(feature = "ring")]
+pub fn verify_signature(secret: &str, body: &[u8], signature_header: &str) -> bool {
+    let Some(signature) = decode_hex(signature_header.trim()) else {
+        return false;
+    };
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, secret.as_bytes());
+
+    ring::hmac::verify(&key, body, &signature).is_ok()
+}
+
+#[cfg(feature = "ring")]
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn one() -> u32 {
+    1
+}
+
+/// An amount that webhooks send as either a number or a string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Scalar {
+    Number(serde_json::Number),
+    String(String),
+}
+
+/// Parses an amount sent as either a number or a string.
+fn price<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Price, D::Error> {
+    let price = match Scalar::deserialize(deserializer)? {
+        Scalar::Number(number) => number.to_string(),
+        Scalar::String(string) => string,
+    };
+
+    #[cfg(feature = "rust_decimal")]
+    return price.trim().parse().map_err(serde::de::Error::custom);
+
+    #[cfg(not(feature = "rust_decimal"))]
+    Ok(price)
+}
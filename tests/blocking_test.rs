@@ -0,0 +1,120 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use buy_me_a_coffee::{blocking::Client, Error, MemberStatus};
+use serde_json::json;
+use tokio::runtime::Runtime;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Starts a mock server on its own runtime, since the blocking client can't be
+/// used from within one.
+fn mock(mocks: Vec<Mock>) -> (Runtime, MockServer, Client) {
+    let runtime = Runtime::new().unwrap();
+    let server = runtime.block_on(async {
+        let server = MockServer::start().await;
+
+        for mock in mocks {
+            mock.mount(&server).await;
+        }
+
+        server
+    });
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .build_blocking()
+        .unwrap();
+
+    (runtime, server, client)
+}
+
+#[test]
+fn blocking_members() {
+    let (_runtime, _server, client) =
+        mock(vec![Mock::given(method("GET"))
+            .and(path("/v1/subscriptions"))
+            .and(query_param("status", "active"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                common::page(1, 1, vec![common::membership()]),
+            ))]);
+
+    let page = client.members(MemberStatus::Active, 1).unwrap();
+
+    assert_eq!(page.data[0].id, 2345);
+}
+
+#[test]
+fn blocking_single_items() {
+    let (_runtime, _server, client) = mock(vec![
+        Mock::given(method("GET"))
+            .and(path("/v1/subscriptions/2345"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::membership())),
+        Mock::given(method("GET"))
+            .and(path("/v1/supporters/245731"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::support())),
+        Mock::given(method("GET"))
+            .and(path("/v1/extras/1111"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::purchase())),
+    ]);
+
+    assert_eq!(client.membership(2345).unwrap().id, 2345);
+    assert_eq!(client.support(245731).unwrap().id, 245731);
+    assert_eq!(client.extra(1111).unwrap().id, 1111);
+}
+
+#[test]
+fn blocking_errors_match_async() {
+    let (_runtime, _server, client) = mock(vec![
+        Mock::given(method("GET"))
+            .and(path("/v1/supporters"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "error": "No supporters" })),
+            ),
+        Mock::given(method("GET"))
+            .and(path("/v1/extras"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("<html></html>", "text/html")),
+    ]);
+
+    let err = client.supporters_raw(1).unwrap_err();
+
+    assert!(matches!(err, Error::Server(ref err) if err.reason == "No supporters"));
+    assert!(client.supporters(1).unwrap().data.is_empty());
+
+    let err = client.extras(1).unwrap_err();
+
+    assert!(matches!(err, Error::Unauthorized { redirected: true }));
+}
+
+#[test]
+fn blocking_page_out_of_range() {
+    let (_runtime, _server, client) = mock(vec![
+        Mock::given(method("GET"))
+            .and(path("/v1/extras"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+                1,
+                1,
+                vec![common::purchase()],
+            )))
+            .with_priority(1),
+        Mock::given(method("GET"))
+            .and(path("/v1/extras"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "error": "No extra purchases" })),
+            ),
+    ]);
+
+    client.extras(1).unwrap();
+
+    assert!(matches!(
+        client.extras(2),
+        Err(Error::PageOutOfRange {
+            requested: 2,
+            last_page: 1,
+        }),
+    ));
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "cache")]
+
+mod common;
+
+use std::time::Duration;
+
+use buy_me_a_coffee::Client;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+async fn caching_mock(ttl: Duration) -> (MockServer, Client) {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .cache_ttl(ttl)
+        .build()
+        .unwrap();
+
+    (server, client)
+}
+
+#[tokio::test]
+async fn cache_hit_skips_request() {
+    let (server, client) = caching_mock(Duration::from_secs(60)).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let first = client.support(245731).await.unwrap();
+    let second = client.clone().support(245731).await.unwrap();
+
+    assert_eq!(first.id, second.id);
+}
+
+#[tokio::test]
+async fn cache_is_keyed_by_query() {
+    let (server, client) = caching_mock(Duration::from_secs(60)).await;
+
+    for page in 1..=2 {
+        Mock::given(method("GET"))
+            .and(path("/v1/supporters"))
+            .and(query_param("page", page.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+                page,
+                2,
+                vec![common::support()],
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    assert_eq!(client.supporters(1).await.unwrap().current_page, 1);
+    assert_eq!(client.supporters(2).await.unwrap().current_page, 2);
+    assert_eq!(client.supporters(1).await.unwrap().current_page, 1);
+}
+
+#[tokio::test]
+async fn expired_and_cleared_entries_are_refetched() {
+    let (server, client) = caching_mock(Duration::from_millis(50)).await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    client.support(245731).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    client.support(245731).await.unwrap();
+    client.clear_cache();
+    client.support(245731).await.unwrap();
+}
+
+#[tokio::test]
+async fn errors_are_not_cached() {
+    let (server, client) = caching_mock(Duration::from_secs(60)).await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    client.support(0).await.unwrap_err();
+    client.support(0).await.unwrap_err();
+}
+
+#[tokio::test]
+async fn expired_entries_are_pruned() {
+    let (server, client) = caching_mock(Duration::from_millis(50)).await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .mount(&server)
+        .await;
+
+    client.support(1).await.unwrap();
+    client.support(2).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    client.support(3).await.unwrap();
+
+    assert!(format!("{client:?}").contains("len: 1"), "{client:?}");
+}
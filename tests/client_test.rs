@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use buy_me_a_coffee::{Client, Error, MemberStatus};
+
+#[test]
+fn try_new_accepts_token() {
+    Client::try_new("personal access token").unwrap();
+}
+
+#[test]
+fn from_file_trims_token() {
+    let dir = std::env::temp_dir().join(format!("buy-me-a-coffee-token-{}", std::process::id()));
+
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("token"), "personal access token\n").unwrap();
+    std::fs::write(dir.join("empty"), "\n").unwrap();
+
+    let client = Client::from_file(dir.join("token")).unwrap();
+
+    assert!(format!("{client:?}").contains(&"*".repeat("personal access token".len())));
+    assert!(matches!(
+        Client::from_file(dir.join("empty")),
+        Err(Error::InvalidToken),
+    ));
+    assert!(matches!(
+        Client::from_file(dir.join("missing")),
+        Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound,
+    ));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn build_members_request_without_sending() {
+    let client = Client::builder("test token")
+        .user_agent("my-app/1.0")
+        .per_page(50)
+        .build()
+        .unwrap();
+    let request = client
+        .build_members_request(MemberStatus::Inactive, 3)
+        .unwrap();
+
+    assert_eq!(request.method(), "GET");
+    assert_eq!(
+        request.url().as_str(),
+        "https://developers.buymeacoffee.com/api/v1/subscriptions?status=inactive&page=3&per_page=50",
+    );
+    assert_eq!(request.headers()["authorization"], "Bearer test token");
+    assert_eq!(request.headers()["user-agent"], "my-app/1.0");
+}
+
+#[test]
+fn builds_with_connection_options() {
+    let builder = Client::builder("test token")
+        .pool_idle_timeout(Duration::from_secs(30))
+        .tcp_keepalive(Duration::from_secs(60));
+
+    #[cfg(feature = "blocking")]
+    builder.clone().build_blocking().unwrap();
+    builder.build().unwrap();
+}
+
+#[test]
+fn new_accepts_empty_token() {
+    Client::new("");
+    Client::new(" \n\t");
+
+    #[cfg(feature = "blocking")]
+    buy_me_a_coffee::blocking::Client::new("");
+}
+
+#[test]
+fn try_new_rejects_empty_token() {
+    assert!(matches!(Client::try_new(""), Err(Error::InvalidToken)));
+    assert!(matches!(Client::try_new(" \n\t"), Err(Error::InvalidToken)));
+}
+
+#[test]
+fn from_env_reads_token() {
+    std::env::set_var("BUYMEACOFFEE_TOKEN", "personal access token");
+
+    Client::from_env().unwrap();
+}
+
+#[test]
+fn from_env_var_rejects_missing_or_empty_token() {
+    std::env::remove_var("BUY_ME_A_COFFEE_TEST_MISSING");
+    std::env::set_var("BUY_ME_A_COFFEE_TEST_EMPTY", "");
+
+    assert!(matches!(
+        Client::from_env_var("BUY_ME_A_COFFEE_TEST_MISSING"),
+        Err(Error::InvalidToken),
+    ));
+    assert!(matches!(
+        Client::from_env_var("BUY_ME_A_COFFEE_TEST_EMPTY"),
+        Err(Error::InvalidToken),
+    ));
+}
+
+#[test]
+fn build_rejects_empty_token() {
+    assert!(matches!(
+        Client::builder(" ").build(),
+        Err(Error::InvalidToken),
+    ));
+}
+
+#[test]
+fn build_rejects_invalid_base_url() {
+    for base_url in ["", "localhost:8080", "not a url", "ftp://example.com"] {
+        let result = Client::builder("token").base_url(base_url).build();
+
+        assert!(
+            matches!(result, Err(Error::InvalidConfig(_))),
+            "{base_url:?} should be rejected",
+        );
+    }
+
+    Client::builder("token")
+        .base_url("http://localhost:8080/")
+        .build()
+        .unwrap();
+}
+
+#[test]
+fn build_rejects_pointless_retry() {
+    assert!(matches!(
+        Client::builder("token")
+            .retry(0, Duration::from_millis(100))
+            .build(),
+        Err(Error::InvalidConfig(_)),
+    ));
+    assert!(matches!(
+        Client::builder("token").retry(3, Duration::ZERO).build(),
+        Err(Error::InvalidConfig(_)),
+    ));
+}
+
+#[test]
+fn build_rejects_zero_rate_limit() {
+    assert!(matches!(
+        Client::builder("token").rate_limit(0).build(),
+        Err(Error::InvalidConfig(_)),
+    ));
+}
+
+#[test]
+fn build_rejects_zero_per_page() {
+    assert!(matches!(
+        Client::builder("token").per_page(0).build(),
+        Err(Error::InvalidConfig(_)),
+    ));
+}
+
+#[test]
+fn build_rejects_invalid_user_agent() {
+    assert!(matches!(
+        Client::builder("token").user_agent("my-app\n").build(),
+        Err(Error::InvalidConfig(_)),
+    ));
+}
@@ -0,0 +1,147 @@
+#![allow(dead_code)]
+
+use buy_me_a_coffee::Client;
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+pub fn membership() -> Value {
+    json!({
+        "subscription_id": 2345,
+        "subscription_cancelled_on": null,
+        "subscription_created_on": "2020-09-17 14:41:16",
+        "subscription_updated_on": "2020-09-17 14:41:16",
+        "subscription_current_period_start": "2020-09-17 14:41:16",
+        "subscription_current_period_end": "2020-10-17 14:41:16",
+        "subscription_coffee_price": "5.0000",
+        "subscription_coffee_num": 1,
+        "subscription_is_cancelled": false,
+        "subscription_is_cancelled_at_period_end": false,
+        "subscription_currency": "USD",
+        "subscription_message": null,
+        "message_visibility": 1,
+        "subscription_duration_type": "month",
+        "referer": null,
+        "country": "US",
+        "transaction_id": "pi_1HSH2NJEtINljGAa0hZ2DgIi",
+        "payer_email": "jane@example.com",
+        "payer_name": "Jane Doe"
+    })
+}
+
+pub fn support() -> Value {
+    json!({
+        "support_id": 245731,
+        "support_note": "Thanks for the good work",
+        "support_coffees": 1,
+        "transaction_id": "pi_1HFtSpJEtINljGAa0FNgyR8m",
+        "support_visibility": 1,
+        "support_created_on": "2020-08-13 17:56:56",
+        "support_updated_on": "2020-08-13 17:56:56",
+        "transfer_id": null,
+        "supporter_name": "John Doe",
+        "support_coffee_price": "5.0000",
+        "support_email": "john@example.com",
+        "is_refunded": false,
+        "support_currency": "USD",
+        "support_note_pinned": 0,
+        "referer": null,
+        "country": "US",
+        "payer_email": "john@example.com",
+        "payment_platform": "stripe",
+        "payer_name": "John Doe"
+    })
+}
+
+pub fn purchase() -> Value {
+    json!({
+        "purchase_id": 1111,
+        "purchased_on": "2020-09-17 08:30:21",
+        "purchase_updated_on": "2020-09-17 08:30:21",
+        "purchase_is_revoked": false,
+        "purchase_amount": "5.00",
+        "purchase_currency": "USD",
+        "purchase_question": "What's your discord username?",
+        "payer_email": "john@example.com",
+        "payer_name": "John Doe",
+        "extra": {
+            "reward_id": 25,
+            "reward_title": "Sticker pack",
+            "reward_description": "A pack of stickers",
+            "reward_confirmation_message": "Thanks!",
+            "reward_question": "What's your discord username?",
+            "reward_used": 1,
+            "reward_created_on": "2020-09-01 10:00:00",
+            "reward_updated_on": "2020-09-01 10:00:00",
+            "reward_deleted_on": null,
+            "reward_is_active": true,
+            "reward_image": "https://cdn.buymeacoffee.com/uploads/rewards/sticker-pack.png",
+            "reward_slots": 10,
+            "reward_coffee_price": "5.00",
+            "reward_order": 0
+        }
+    })
+}
+
+pub async fn mock() -> (MockServer, Client) {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+
+    (server, client)
+}
+
+/// Builds a page like the ones the API returns, with `per_page` set to the
+/// number of items in `data`.
+pub fn page(current_page: u16, last_page: u16, data: Vec<Value>) -> Value {
+    let per_page = data.len() as u16;
+    let from = (current_page - 1) * per_page + 1;
+
+    json!({
+        "current_page": current_page,
+        "data": data,
+        "from": from,
+        "last_page": last_page,
+        "per_page": per_page,
+        "to": from + per_page - 1,
+        "total": last_page * per_page,
+    })
+}
+
+/// Mounts one mock per page of `endpoint`, each returning the given items.
+pub async fn mount_pages(server: &MockServer, endpoint: &str, pages: Vec<Vec<Value>>) {
+    let last_page = pages.len() as u16;
+
+    for (index, data) in pages.into_iter().enumerate() {
+        let current_page = index as u16 + 1;
+
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .and(query_param("page", current_page.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page(
+                current_page,
+                last_page,
+                data,
+            )))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+}
+
+pub async fn mount_empty(server: &MockServer, endpoint: &str, reason: &str) {
+    Mock::given(method("GET"))
+        .and(path(endpoint))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "error": reason })))
+        .mount(server)
+        .await;
+}
+
+pub fn with_id(mut value: Value, key: &str, id: u32) -> Value {
+    value[key] = id.into();
+    value
+}
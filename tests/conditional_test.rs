@@ -0,0 +1,115 @@
+mod common;
+
+use buy_me_a_coffee::{Error, MemberStatus};
+use wiremock::{
+    matchers::{header, method, path, query_param},
+    Mock, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn not_modified_returns_none() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(header("if-none-match", "\"abc\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"abc\"")
+                .insert_header("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT")
+                .set_body_json(common::page(1, 1, vec![common::support()])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let page = client.supporters_if_modified(1).await.unwrap().unwrap();
+
+    assert_eq!(page.data[0].id, 245731);
+    assert!(client
+        .clone()
+        .supporters_if_modified(1)
+        .await
+        .unwrap()
+        .is_none());
+
+    let requests = server.received_requests().await.unwrap();
+
+    assert_eq!(
+        requests[1].headers["if-modified-since"],
+        "Wed, 21 Oct 2015 07:28:00 GMT",
+    );
+}
+
+#[tokio::test]
+async fn validators_are_kept_per_page() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/extras"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"abc\"")
+                .set_body_json(common::page(1, 2, vec![common::purchase()])),
+        )
+        .mount(&server)
+        .await;
+
+    client.extras_if_modified(1).await.unwrap();
+
+    assert!(client.extras_if_modified(2).await.unwrap().is_some());
+
+    let requests = server.received_requests().await.unwrap();
+
+    assert!(!requests[1].headers.contains_key("if-none-match"));
+}
+
+#[tokio::test]
+async fn empty_account_is_an_empty_page() {
+    let (server, client) = common::mock().await;
+
+    common::mount_empty(&server, "/v1/subscriptions", "No subscriptions").await;
+
+    let page = client
+        .members_if_modified(MemberStatus::All, 1)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(page.is_empty());
+}
+
+#[tokio::test]
+async fn past_last_page_is_out_of_range() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            1,
+            vec![common::support()],
+        )))
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    common::mount_empty(&server, "/v1/supporters", "No supporters").await;
+
+    client.supporters_if_modified(1).await.unwrap();
+
+    assert!(matches!(
+        client.supporters_if_modified(2).await,
+        Err(Error::PageOutOfRange {
+            requested: 2,
+            last_page: 1,
+        }),
+    ));
+}
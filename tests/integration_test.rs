@@ -1,26 +1,162 @@
-use std::env;
+mod common;
 
-use buy_me_a_coffee::MemberStatus;
-use dotenvy::dotenv;
+use std::time::{Duration, Instant};
 
-fn test_pat() -> String {
-    dotenv().ok();
+use buy_me_a_coffee::{Client, Error, MemberStatus};
+use futures::TryStreamExt;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    StatusCode,
+};
+use serde_json::json;
+use wiremock::{
+    matchers::{header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
 
-    env::var("TEST_PAT").expect("a personal access token must be provided to run tests")
+#[tokio::test]
+#[should_panic = "Unauthorized { redirected: true }"]
+async fn unauthorized_error() {
+    let (server, client) = common::mock().await;
+
+    // The API redirects to the login page when the token is invalid
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw("<!DOCTYPE html><html></html>", "text/html"),
+        )
+        .mount(&server)
+        .await;
+
+    client.members(MemberStatus::All, 1).await.unwrap();
 }
 
 #[tokio::test]
-#[should_panic = "Client(401)"]
-async fn unauthorized_error() {
-    let client = buy_me_a_coffee::Client::new("invalid access token");
+async fn html_is_kept_when_not_treated_as_unauthorized() {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .treat_html_as_unauthorized(false)
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw("<html>Under maintenance</html>", "text/html"),
+        )
+        .mount(&server)
+        .await;
+
+    let err = client.members(MemberStatus::All, 1).await.unwrap_err();
+
+    assert!(
+        matches!(&err, Error::Deserialize { body, .. } if body == "<html>Under maintenance</html>"),
+        "{err:?}",
+    );
+}
+
+#[tokio::test]
+async fn unexpected_shape_is_untyped_when_enabled() {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .untyped_fallback(true)
+        .build()
+        .unwrap();
+    let mut support = common::support();
+
+    support["support_coffees"] = "three".into();
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&support))
+        .mount(&server)
+        .await;
+
+    let err = client.support(245731).await.unwrap_err();
+
+    assert!(
+        matches!(&err, Error::Untyped(value) if *value == support),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn empty_body_is_an_empty_response() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let err = client.support(245731).await.unwrap_err();
+
+    assert!(
+        matches!(err, Error::EmptyResponse(StatusCode::OK)),
+        "{err:?}"
+    );
+    assert!(client.supporters(1).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+#[should_panic = "Unauthorized { redirected: false }"]
+async fn unauthorized_status_error() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(401).set_body_json(json!({ "error": "Unauthenticated." })),
+        )
+        .mount(&server)
+        .await;
 
     client.members(MemberStatus::All, 1).await.unwrap();
 }
 
+#[tokio::test]
+async fn validate_token_distinguishes_rejection() {
+    let (server, client) = common::mock().await;
+
+    common::mount_empty(&server, "/v1/subscriptions", "No subscriptions").await;
+
+    assert!(client.validate_token().await.unwrap());
+
+    server.reset().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw("<!DOCTYPE html><html></html>", "text/html"),
+        )
+        .mount(&server)
+        .await;
+
+    assert!(!client.validate_token().await.unwrap());
+
+    server.reset().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    assert!(!client.validate_token().await.unwrap());
+
+    server.reset().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    assert!(client.validate_token().await.is_err());
+}
+
 #[tokio::test]
 #[should_panic = "Client(404)"]
 async fn not_found_error() {
-    let client = buy_me_a_coffee::Client::new(&test_pat());
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions/0"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
 
     client.membership(0).await.unwrap();
 }
@@ -28,25 +164,531 @@ async fn not_found_error() {
 #[tokio::test]
 #[should_panic = "No subscriptions"]
 async fn no_subscriptions_error() {
-    let client = buy_me_a_coffee::Client::new(&test_pat());
+    let (server, client) = common::mock().await;
 
-    client.members(MemberStatus::All, 1).await.unwrap();
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "error": "No subscriptions" })),
+        )
+        .mount(&server)
+        .await;
+
+    client.members_raw(MemberStatus::All, 1).await.unwrap();
 }
 
 #[tokio::test]
 #[should_panic = "No supporters"]
 async fn no_supporters_error() {
-    let client = buy_me_a_coffee::Client::new(&test_pat());
+    let (server, client) = common::mock().await;
 
-    client.supporters(1).await.unwrap();
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "error": "No supporters" })))
+        .mount(&server)
+        .await;
+
+    client.supporters_raw(1).await.unwrap();
 }
 
 #[tokio::test]
 #[should_panic = "No extra purchases"]
 async fn no_extras_error() {
-    let client = buy_me_a_coffee::Client::new(&test_pat());
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/extras"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "error": "No extra purchases" })),
+        )
+        .mount(&server)
+        .await;
+
+    client.extras_raw(1).await.unwrap();
+}
+
+#[tokio::test]
+async fn server_errors_carry_status() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "error": "No supporters" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/extras/0"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "error_code": 404,
+            "reason": "Extra not found",
+        })))
+        .mount(&server)
+        .await;
+
+    let err = client.supporters_raw(1).await.unwrap_err();
+
+    assert!(matches!(err, Error::Server(ref err) if err.status == 200));
+
+    let err = client.extra(0).await.unwrap_err();
+
+    assert!(matches!(
+        err,
+        Error::Server(ref err) if err.status == 404 && err.reason == "Extra not found",
+    ));
+}
+
+#[tokio::test]
+async fn no_subscriptions_is_empty_page() {
+    let (server, client) = common::mock().await;
+
+    common::mount_empty(&server, "/v1/subscriptions", "No subscriptions").await;
+
+    let page = client.members(MemberStatus::All, 1).await.unwrap();
+
+    assert!(page.data.is_empty());
+    assert_eq!(page.total, 0);
+}
+
+#[tokio::test]
+async fn no_supporters_is_empty_page() {
+    let (server, client) = common::mock().await;
+
+    common::mount_empty(&server, "/v1/supporters", "No supporters").await;
+
+    let page = client.supporters(1).await.unwrap();
+
+    assert!(page.data.is_empty());
+    assert_eq!(page.total, 0);
+}
+
+#[tokio::test]
+async fn no_extras_is_empty_page() {
+    let (server, client) = common::mock().await;
+
+    common::mount_empty(&server, "/v1/extras", "No extra purchases").await;
+
+    let page = client.extras(1).await.unwrap();
+
+    assert!(page.data.is_empty());
+    assert_eq!(page.total, 0);
+}
+
+#[tokio::test]
+async fn sends_token() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .and(header("authorization", "Bearer test token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert_eq!(client.support(245731).await.unwrap().id, 245731);
+}
+
+#[tokio::test]
+async fn members_stream_walks_pages() {
+    let (server, client) = common::mock().await;
+
+    for page in 1..=3 {
+        let mut membership = common::membership();
+
+        membership["subscription_id"] = page.into();
+
+        Mock::given(method("GET"))
+            .and(path("/v1/subscriptions"))
+            .and(query_param("page", page.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+                page,
+                3,
+                vec![membership.clone(), membership],
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let ids: Vec<u32> = client
+        .members_stream(MemberStatus::All)
+        .map_ok(|membership| membership.id)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(ids, [1, 1, 2, 2, 3, 3]);
+}
+
+#[tokio::test]
+async fn members_stream_fetches_lazily() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            3,
+            vec![common::membership()],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            2,
+            3,
+            vec![common::membership()],
+        )))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let mut stream = Box::pin(client.members_stream(MemberStatus::All));
 
-    client.extras(1).await.unwrap();
+    stream.try_next().await.unwrap().unwrap();
 }
 
-// TODO: Add more tests once... I actually have any supporters...
+#[tokio::test]
+async fn members_stream_prefetch_walks_pages() {
+    let (server, client) = common::mock().await;
+
+    for page in 1..=3 {
+        let membership = common::with_id(common::membership(), "subscription_id", page.into());
+
+        Mock::given(method("GET"))
+            .and(path("/v1/subscriptions"))
+            .and(query_param("page", page.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+                page,
+                3,
+                vec![membership.clone(), membership],
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let ids: Vec<u32> = client
+        .members_stream_prefetch(MemberStatus::All, 2)
+        .map_ok(|membership| membership.id)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(ids, [1, 1, 2, 2, 3, 3]);
+}
+
+#[tokio::test]
+async fn members_stream_prefetch_fetches_ahead() {
+    let (server, client) = common::mock().await;
+
+    for page in 1..=3 {
+        Mock::given(method("GET"))
+            .and(path("/v1/subscriptions"))
+            .and(query_param("page", page.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+                page,
+                3,
+                vec![common::membership(), common::membership()],
+            )))
+            .expect(if page == 3 { 0 } else { 1 })
+            .mount(&server)
+            .await;
+    }
+
+    let mut stream = Box::pin(client.members_stream_prefetch(MemberStatus::All, 1));
+
+    stream.try_next().await.unwrap().unwrap();
+
+    // The second page is requested before the first has been consumed
+    for _ in 0..100 {
+        if server.received_requests().await.unwrap().len() == 2 {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn members_stream_ends_on_no_subscriptions() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "error": "No subscriptions" })),
+        )
+        .mount(&server)
+        .await;
+
+    let members: Vec<_> = client
+        .members_stream(MemberStatus::All)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert!(members.is_empty());
+}
+
+#[tokio::test]
+async fn uses_provided_reqwest_client() {
+    let server = MockServer::start().await;
+    let reqwest_client = reqwest::Client::builder()
+        .default_headers(HeaderMap::from_iter([(
+            HeaderName::from_static("x-custom"),
+            HeaderValue::from_static("yes"),
+        )]))
+        .build()
+        .unwrap();
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .reqwest_client(reqwest_client)
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .and(header("x-custom", "yes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client.support(245731).await.unwrap();
+}
+
+#[tokio::test]
+async fn timeout_fires() {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .timeout(Duration::from_secs(2))
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(common::support())
+                .set_delay(Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+
+    let started = Instant::now();
+    let err = client.support(245731).await.unwrap_err();
+
+    assert!(
+        matches!(err, Error::Reqwest(ref err) if err.is_timeout()),
+        "{err:?}"
+    );
+    assert!(started.elapsed() < Duration::from_secs(4));
+}
+
+#[tokio::test]
+async fn sends_default_user_agent() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(header(
+            "user-agent",
+            concat!("buy-me-a-coffee-rs/", env!("CARGO_PKG_VERSION")),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client.support(245731).await.unwrap();
+}
+
+#[tokio::test]
+async fn sends_custom_user_agent() {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .user_agent("my-app/1.0")
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .and(header("user-agent", "my-app/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client.support(245731).await.unwrap();
+}
+
+#[tokio::test]
+async fn sends_per_page() {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .per_page(100)
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(query_param("page", "1"))
+        .and(query_param("per_page", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            1,
+            vec![common::support()],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client.supporters(1).await.unwrap();
+}
+
+#[tokio::test]
+async fn unexpected_shape_is_deserialize_error() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "unexpected": true })))
+        .mount(&server)
+        .await;
+
+    let err = client.support(245731).await.unwrap_err();
+
+    assert!(
+        matches!(err, Error::Deserialize { ref body, .. } if body == r#"{"unexpected":true}"#),
+        "{err:?}",
+    );
+}
+
+#[tokio::test]
+async fn unexpected_body_is_truncated() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("é".repeat(4096)))
+        .mount(&server)
+        .await;
+
+    let Error::Deserialize { body, .. } = client.support(245731).await.unwrap_err() else {
+        panic!("expected a deserialization error");
+    };
+
+    assert_eq!(body, format!("{}…", "é".repeat(1024)));
+}
+
+#[tokio::test]
+async fn get_raw_requests_any_endpoint() {
+    #[derive(serde::Deserialize)]
+    struct Posts {
+        posts: Vec<String>,
+    }
+
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/posts"))
+        .and(query_param("status", "draft"))
+        .and(header("authorization", "Bearer test token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "posts": ["Hello"] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let posts: Posts = client
+        .get_raw("/v1/posts", &[("status".to_string(), "draft".to_string())])
+        .await
+        .unwrap();
+
+    assert_eq!(posts.posts, ["Hello"]);
+}
+
+#[tokio::test]
+async fn sends_through_proxy() {
+    let proxy = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url("http://api.buymeacoffee.invalid")
+        .proxy(reqwest::Proxy::http(proxy.uri()).unwrap())
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .and(header("host", "api.buymeacoffee.invalid"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .expect(1)
+        .mount(&proxy)
+        .await;
+
+    client.support(245731).await.unwrap();
+}
+
+#[tokio::test]
+async fn memberships_are_aligned_with_ids() {
+    let (server, client) = common::mock().await;
+
+    for id in [1, 3] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/subscriptions/{id}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::with_id(
+                common::membership(),
+                "subscription_id",
+                id,
+            )))
+            .mount(&server)
+            .await;
+    }
+
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions/2"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let results = client.memberships(&[3, 2, 1]).await;
+
+    assert_eq!(results[0].as_ref().unwrap().id, 3);
+    assert!(matches!(
+        results[1],
+        Err(Error::Client(StatusCode::NOT_FOUND))
+    ));
+    assert_eq!(results[2].as_ref().unwrap().id, 1);
+}
+
+#[tokio::test]
+async fn decodes_compressed_responses() {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+    use wiremock::matchers::header_regex;
+
+    let (server, client) = common::mock().await;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+
+    encoder
+        .write_all(common::support().to_string().as_bytes())
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .and(header_regex("accept-encoding", "gzip"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .insert_header("content-type", "application/json")
+                .set_body_bytes(encoder.finish().unwrap()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert_eq!(client.support(245731).await.unwrap().id, 245731);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "log")]
+
+mod common;
+
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+static LOGGER: Captured = Captured(Mutex::new(Vec::new()));
+
+struct Captured(Mutex<Vec<String>>);
+
+impl Log for Captured {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Debug
+    }
+
+    fn log(&self, record: &Record) {
+        if record.target().starts_with("buy_me_a_coffee") {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[tokio::test]
+async fn logs_request_and_response() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Debug);
+
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .mount(&server)
+        .await;
+
+    client.support(245731).await.unwrap();
+
+    let logged = LOGGER.0.lock().unwrap();
+
+    assert_eq!(
+        *logged,
+        [
+            format!(
+                "GET {}/v1/supporters/245731 with token **********",
+                server.uri()
+            ),
+            format!(
+                "GET {}/v1/supporters/245731 responded with 200 OK",
+                server.uri()
+            ),
+        ],
+    );
+}
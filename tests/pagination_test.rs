@@ -0,0 +1,1200 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use buy_me_a_coffee::{
+    Error, ListParams, MemberBreakdown, MemberStatus, RewardAvailability, SupportSortKey,
+};
+use common::{mount_empty, mount_pages, with_id};
+use futures::TryStreamExt;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn all_members_concatenates_pages() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![
+            vec![
+                with_id(common::membership(), "subscription_id", 1),
+                with_id(common::membership(), "subscription_id", 2),
+            ],
+            vec![with_id(common::membership(), "subscription_id", 3)],
+        ],
+    )
+    .await;
+
+    let ids: Vec<u32> = client
+        .all_members(MemberStatus::All)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|membership| membership.id)
+        .collect();
+
+    assert_eq!(ids, [1, 2, 3]);
+}
+
+#[tokio::test]
+async fn all_supporters_concatenates_pages() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        vec![
+            vec![with_id(common::support(), "support_id", 1)],
+            vec![with_id(common::support(), "support_id", 2)],
+        ],
+    )
+    .await;
+
+    assert_eq!(client.all_supporters().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn all_extras_concatenates_pages() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/extras",
+        vec![
+            vec![with_id(common::purchase(), "purchase_id", 1)],
+            vec![with_id(common::purchase(), "purchase_id", 2)],
+        ],
+    )
+    .await;
+
+    assert_eq!(client.all_extras().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn all_methods_are_empty_without_data() {
+    let (server, client) = common::mock().await;
+
+    mount_empty(&server, "/v1/subscriptions", "No subscriptions").await;
+    mount_empty(&server, "/v1/supporters", "No supporters").await;
+    mount_empty(&server, "/v1/extras", "No extra purchases").await;
+
+    assert!(client
+        .all_members(MemberStatus::All)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(client.all_supporters().await.unwrap().is_empty());
+    assert!(client.all_extras().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn counts_read_first_page_total() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            1,
+            vec![common::membership(), common::membership()],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            3,
+            vec![common::support()],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert_eq!(client.members_count(MemberStatus::All).await.unwrap(), 2);
+    assert_eq!(client.supporters_count().await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn counts_are_zero_without_data() {
+    let (server, client) = common::mock().await;
+
+    mount_empty(&server, "/v1/subscriptions", "No subscriptions").await;
+    mount_empty(&server, "/v1/supporters", "No supporters").await;
+    mount_empty(&server, "/v1/extras", "No extra purchases").await;
+
+    assert_eq!(client.members_count(MemberStatus::All).await.unwrap(), 0);
+    assert_eq!(client.supporters_count().await.unwrap(), 0);
+    assert_eq!(client.extras_count().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn member_breakdown_counts_every_page() {
+    let (server, client) = common::mock().await;
+    let membership = |duration_type: &str| {
+        let mut membership = common::membership();
+
+        membership["subscription_duration_type"] = duration_type.into();
+        membership
+    };
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![
+            vec![membership("month"), membership("year")],
+            vec![membership("month"), membership("week")],
+        ],
+    )
+    .await;
+
+    let breakdown = client.member_breakdown(MemberStatus::All).await.unwrap();
+
+    assert_eq!(
+        breakdown,
+        MemberBreakdown {
+            monthly: 2,
+            yearly: 1,
+            other: 1,
+        },
+    );
+    assert_eq!(breakdown.total(), 4);
+}
+
+#[tokio::test]
+async fn capped_walks_stop_at_max_pages() {
+    let (server, client) = common::mock().await;
+
+    for page in 1..=3 {
+        Mock::given(method("GET"))
+            .and(path("/v1/supporters"))
+            .and(query_param("page", page.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+                page,
+                3,
+                vec![with_id(common::support(), "support_id", page.into())],
+            )))
+            .expect(if page == 3 { 0 } else { 1 })
+            .mount(&server)
+            .await;
+    }
+
+    let capped = client.all_supporters_capped(2).await.unwrap();
+    let ids: Vec<_> = capped.data.iter().map(|support| support.id).collect();
+
+    assert_eq!(ids, [1, 2]);
+    assert!(capped.truncated);
+}
+
+#[tokio::test]
+async fn deadline_trips_during_slow_page() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            2,
+            vec![common::support()],
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(query_param("page", "2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(common::page(2, 2, vec![common::support()]))
+                .set_delay(Duration::from_secs(5)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let started = Instant::now();
+    let err = client
+        .all_supporters_until(started + Duration::from_millis(300))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::DeadlineExceeded), "{err:?}");
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn capped_walks_are_complete_within_max_pages() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/extras",
+        vec![vec![common::purchase()], vec![common::purchase()]],
+    )
+    .await;
+    mount_empty(&server, "/v1/subscriptions", "No subscriptions").await;
+
+    let capped = client.all_extras_capped(5).await.unwrap();
+
+    assert_eq!(capped.data.len(), 2);
+    assert!(!capped.truncated);
+
+    let capped = client
+        .all_members_capped(MemberStatus::All, 5)
+        .await
+        .unwrap();
+
+    assert!(capped.data.is_empty());
+    assert!(!capped.truncated);
+}
+
+#[tokio::test]
+async fn support_with_membership_matches_payer_email() {
+    let (server, client) = common::mock().await;
+    let mut member = with_id(common::membership(), "subscription_id", 9);
+    let mut anonymous = with_id(common::support(), "support_id", 1);
+
+    member["payer_email"] = "John@Example.com".into();
+    anonymous["payer_email"] = "".into();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(anonymous))
+        .mount(&server)
+        .await;
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![vec![common::membership()], vec![member]],
+    )
+    .await;
+
+    let (support, membership) = client.support_with_membership(245731).await.unwrap();
+
+    assert_eq!(support.id, 245731);
+    assert_eq!(membership.map(|membership| membership.id), Some(9));
+
+    let (_, membership) = client.support_with_membership(1).await.unwrap();
+
+    assert!(membership.is_none());
+}
+
+#[tokio::test]
+async fn find_member_by_email_short_circuits() {
+    let (server, client) = common::mock().await;
+    let mut other = common::membership();
+    let mut wanted = with_id(common::membership(), "subscription_id", 7);
+
+    other["payer_email"] = "someone@example.com".into();
+    wanted["payer_email"] = "Jane.Doe@Example.com".into();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            3,
+            vec![other.clone()],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(2, 3, vec![wanted])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .and(query_param("page", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(3, 3, vec![other])))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let membership = client
+        .find_member_by_email(MemberStatus::All, "jane.doe@example.com")
+        .await
+        .unwrap();
+
+    assert_eq!(membership.map(|membership| membership.id), Some(7));
+}
+
+#[tokio::test]
+async fn find_member_by_email_returns_none_when_exhausted() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![vec![common::membership()]],
+    )
+    .await;
+
+    let membership = client
+        .find_member_by_email(MemberStatus::All, "nobody@example.com")
+        .await
+        .unwrap();
+
+    assert!(membership.is_none());
+}
+
+#[tokio::test]
+async fn member_pages_advances_until_last_page() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![vec![common::membership()], vec![common::membership()]],
+    )
+    .await;
+
+    let mut pages = client.member_pages(MemberStatus::All);
+
+    assert_eq!(pages.next_page().await.unwrap().unwrap().current_page, 1);
+    assert_eq!(pages.next_page().await.unwrap().unwrap().current_page, 2);
+    assert!(pages.next_page().await.unwrap().is_none());
+    assert!(pages.next_page().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn supporter_and_extra_pages_advance() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(&server, "/v1/supporters", vec![vec![common::support()]]).await;
+    mount_pages(&server, "/v1/extras", vec![vec![common::purchase()]]).await;
+
+    let mut supporter_pages = client.supporter_pages();
+    let mut extra_pages = client.extra_pages();
+
+    assert!(supporter_pages.next_page().await.unwrap().is_some());
+    assert!(supporter_pages.next_page().await.unwrap().is_none());
+    assert!(extra_pages.next_page().await.unwrap().is_some());
+    assert!(extra_pages.next_page().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn page_next_chains_until_last_page() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        (1..=3)
+            .map(|id| vec![with_id(common::membership(), "subscription_id", id)])
+            .collect(),
+    )
+    .await;
+
+    let mut page = client.members(MemberStatus::Active, 1).await.unwrap();
+    let mut ids = vec![page.data[0].id];
+
+    while let Some(next) = page.next(&client, MemberStatus::Active) {
+        page = next.await.unwrap();
+        ids.push(page.data[0].id);
+    }
+
+    assert_eq!(ids, [1, 2, 3]);
+}
+
+#[tokio::test]
+async fn member_page_stream_yields_every_page() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![
+            vec![common::membership(), common::membership()],
+            vec![common::membership()],
+        ],
+    )
+    .await;
+
+    let pages: Vec<_> = client
+        .member_page_stream(MemberStatus::All)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(pages.len(), 2);
+    assert_eq!((pages[0].current_page, pages[0].last_page), (1, 2));
+    assert_eq!(pages[0].data.len(), 2);
+    assert_eq!((pages[1].current_page, pages[1].last_page), (2, 2));
+}
+
+#[tokio::test]
+async fn member_page_stream_is_empty_without_data() {
+    let (server, client) = common::mock().await;
+
+    mount_empty(&server, "/v1/subscriptions", "No subscriptions").await;
+
+    let pages: Vec<_> = client
+        .member_page_stream(MemberStatus::All)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert!(pages.is_empty());
+}
+
+#[tokio::test]
+async fn page_past_last_is_out_of_range() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            2,
+            vec![common::support()],
+        )))
+        .mount(&server)
+        .await;
+    mount_empty(&server, "/v1/supporters", "No supporters").await;
+
+    // Without knowing the last page, this can't be told apart from no data
+    assert!(client.supporters(5).await.unwrap().is_empty());
+
+    client.supporters(1).await.unwrap();
+
+    assert!(matches!(
+        client.clone().supporters(5).await,
+        Err(Error::PageOutOfRange {
+            requested: 5,
+            last_page: 2,
+        }),
+    ));
+}
+
+#[tokio::test]
+async fn pages_are_none_without_data() {
+    let (server, client) = common::mock().await;
+
+    mount_empty(&server, "/v1/supporters", "No supporters").await;
+
+    assert!(client
+        .supporter_pages()
+        .next_page()
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[cfg(feature = "rust_decimal")]
+#[tokio::test]
+async fn total_support_amount_skips_refunds_and_other_currencies() {
+    let (server, client) = common::mock().await;
+    let mut refunded = common::support();
+    let mut euros = common::support();
+
+    refunded["is_refunded"] = true.into();
+    euros["support_currency"] = "EUR".into();
+    euros["support_coffees"] = 3.into();
+
+    let mut garbage = common::support();
+
+    garbage["support_coffee_price"] = "n/a".into();
+
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        vec![vec![common::support(), refunded], vec![euros, garbage]],
+    )
+    .await;
+
+    assert_eq!(
+        client.total_support_amount(Some("usd")).await.unwrap(),
+        "5".parse().unwrap(),
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn members_pending_cancellation_ends_at_period_end() {
+    use chrono::{Duration, Utc};
+
+    let (server, client) = common::mock().await;
+    let membership = |id: u32, is_cancelled_at_period_end: bool, period_end: Duration| {
+        let mut value = with_id(common::membership(), "subscription_id", id);
+
+        value["subscription_is_cancelled"] = is_cancelled_at_period_end.into();
+        value["subscription_is_cancelled_at_period_end"] = is_cancelled_at_period_end.into();
+        value["subscription_current_period_end"] = (Utc::now() + period_end)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+            .into();
+        value
+    };
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![
+            vec![
+                membership(1, true, Duration::minutes(1)),
+                membership(2, true, -Duration::seconds(1)),
+            ],
+            vec![
+                membership(3, false, Duration::days(30)),
+                membership(4, true, Duration::days(30)),
+            ],
+        ],
+    )
+    .await;
+
+    let ids: Vec<_> = client
+        .members_pending_cancellation()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|membership| membership.id)
+        .collect();
+
+    assert_eq!(ids, [1, 4]);
+}
+
+#[cfg(all(feature = "chrono", feature = "rust_decimal"))]
+#[tokio::test]
+async fn monthly_recurring_revenue_spreads_yearly_members() {
+    let (server, client) = common::mock().await;
+    let mut yearly = common::membership();
+    let mut lapsed = common::membership();
+    let mut weekly = common::membership();
+    let mut euros = common::membership();
+
+    yearly["subscription_duration_type"] = "year".into();
+    yearly["subscription_coffee_price"] = "60.0000".into();
+    yearly["subscription_coffee_num"] = 2.into();
+    lapsed["subscription_is_cancelled_at_period_end"] = true.into();
+    weekly["subscription_duration_type"] = "week".into();
+    euros["subscription_currency"] = "EUR".into();
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![
+            vec![common::membership(), yearly, lapsed],
+            vec![weekly, euros],
+        ],
+    )
+    .await;
+
+    assert_eq!(
+        client.monthly_recurring_revenue("usd").await.unwrap(),
+        "15".parse().unwrap(),
+    );
+}
+
+#[tokio::test]
+async fn supporters_sorted_by_coffees() {
+    let (server, client) = common::mock().await;
+    let support = |id, coffees: u32| {
+        let mut support = with_id(common::support(), "support_id", id);
+
+        support["support_coffees"] = coffees.into();
+        support
+    };
+
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        vec![
+            vec![support(1, 3), support(2, 1)],
+            vec![support(3, 5), support(4, 3)],
+        ],
+    )
+    .await;
+
+    let ids: Vec<_> = client
+        .supporters_sorted(SupportSortKey::Coffees, true)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|support| support.id)
+        .collect();
+
+    assert_eq!(ids, [3, 1, 4, 2]);
+}
+
+#[cfg(all(feature = "chrono", feature = "rust_decimal"))]
+#[tokio::test]
+async fn supporters_sorted_by_date_or_amount() {
+    use buy_me_a_coffee::Support;
+
+    let (server, client) = common::mock().await;
+    let support = |id, created_on: &str, price: &str| {
+        let mut support = with_id(common::support(), "support_id", id);
+
+        support["support_created_on"] = created_on.into();
+        support["support_coffee_price"] = price.into();
+        support
+    };
+
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        vec![vec![
+            support(1, "2021-03-01 00:00:00", "3.00"),
+            support(2, "2020-12-31 23:59:59", "garbage"),
+            support(3, "2021-01-01 00:00:00", "10.00"),
+        ]],
+    )
+    .await;
+
+    let mut supporters = client
+        .supporters_sorted(SupportSortKey::CreatedOn, true)
+        .await
+        .unwrap();
+    let ids = |supporters: &[Support]| -> Vec<_> {
+        supporters.iter().map(|support| support.id).collect()
+    };
+
+    assert_eq!(ids(&supporters), [1, 3, 2]);
+
+    supporters.sort_by(|a, b| SupportSortKey::Amount.compare(a, b));
+
+    assert_eq!(ids(&supporters), [2, 1, 3]);
+}
+
+#[tokio::test]
+async fn refunded_supporters_filters_every_page() {
+    let (server, client) = common::mock().await;
+    let mut refunded = with_id(common::support(), "support_id", 2);
+
+    refunded["is_refunded"] = true.into();
+
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        vec![vec![common::support(), refunded], vec![common::support()]],
+    )
+    .await;
+
+    let refunded = client.refunded_supporters().await.unwrap();
+
+    assert_eq!(refunded.len(), 1);
+    assert_eq!(refunded[0].id, 2);
+}
+
+#[tokio::test]
+async fn supporters_min_coffees_filters_every_page() {
+    let (server, client) = common::mock().await;
+    let support = |id: u32, coffees: u32| {
+        let mut support = with_id(common::support(), "support_id", id);
+
+        support["support_coffees"] = coffees.into();
+        support
+    };
+
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        vec![vec![support(1, 1), support(2, 5)], vec![support(3, 3)]],
+    )
+    .await;
+
+    let ids: Vec<u32> = client
+        .supporters_min_coffees(3)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|support| support.id)
+        .collect();
+
+    assert_eq!(ids, [2, 3]);
+}
+
+#[tokio::test]
+async fn supporters_by_country_groups_every_page() {
+    let (server, client) = common::mock().await;
+    let support = |id: u32, country: Option<&str>| {
+        let mut support = with_id(common::support(), "support_id", id);
+
+        support["country"] = country.into();
+        support
+    };
+
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        vec![
+            vec![support(1, Some("US")), support(2, None)],
+            vec![support(3, Some("SE")), support(4, Some("US"))],
+        ],
+    )
+    .await;
+
+    let groups = client.supporters_by_country().await.unwrap();
+    let ids =
+        |country: &str| -> Vec<u32> { groups[country].iter().map(|support| support.id).collect() };
+
+    assert_eq!(groups.len(), 3);
+    assert_eq!(ids("US"), [1, 4]);
+    assert_eq!(ids("SE"), [3]);
+    assert_eq!(ids("unknown"), [2]);
+}
+
+#[tokio::test]
+async fn extras_for_reward_filters_every_page() {
+    let (server, client) = common::mock().await;
+    let purchase = |id, reward_id: u32| {
+        let mut purchase = with_id(common::purchase(), "purchase_id", id);
+
+        purchase["extra"]["reward_id"] = reward_id.into();
+        purchase
+    };
+
+    mount_pages(
+        &server,
+        "/v1/extras",
+        vec![
+            vec![purchase(1, 25), purchase(2, 26)],
+            vec![purchase(3, 26), purchase(4, 25)],
+        ],
+    )
+    .await;
+
+    let ids: Vec<_> = client
+        .extras_for_reward(25)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|purchase| purchase.id)
+        .collect();
+
+    assert_eq!(ids, [1, 4]);
+}
+
+#[tokio::test]
+async fn reward_definitions_keeps_latest_of_each_extra() {
+    let (server, client) = common::mock().await;
+    let purchase = |id, reward_id: u32, title: &str, updated_on: &str| {
+        let mut purchase = with_id(common::purchase(), "purchase_id", id);
+
+        purchase["extra"]["reward_id"] = reward_id.into();
+        purchase["extra"]["reward_title"] = title.into();
+        purchase["extra"]["reward_updated_on"] = updated_on.into();
+        purchase
+    };
+
+    mount_pages(
+        &server,
+        "/v1/extras",
+        vec![
+            vec![
+                purchase(1, 26, "Sticker pack", "2020-09-17 14:41:16"),
+                purchase(2, 25, "Shout-out", "2020-09-17 14:41:16"),
+            ],
+            vec![
+                purchase(3, 26, "Sticker bundle", "2021-01-01 00:00:00"),
+                purchase(4, 25, "Old shout-out", "2020-01-01 00:00:00"),
+            ],
+        ],
+    )
+    .await;
+
+    let titles: Vec<_> = client
+        .reward_definitions()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|extra| (extra.id, extra.title))
+        .collect();
+
+    assert_eq!(
+        titles,
+        [
+            (25, "Shout-out".to_string()),
+            (26, "Sticker bundle".to_string()),
+        ],
+    );
+}
+
+#[tokio::test]
+async fn reward_availability_reports_slots() {
+    let (server, client) = common::mock().await;
+    let purchase = |reward_id: u32, slots: u32, used: u32| {
+        let mut purchase = common::purchase();
+
+        purchase["extra"]["reward_id"] = reward_id.into();
+        purchase["extra"]["reward_slots"] = slots.into();
+        purchase["extra"]["reward_used"] = used.into();
+        purchase
+    };
+
+    mount_pages(
+        &server,
+        "/v1/extras",
+        vec![vec![purchase(25, 10, 4), purchase(26, 2, 2)]],
+    )
+    .await;
+
+    let availability = client.reward_availability(26).await.unwrap().unwrap();
+
+    assert_eq!(
+        availability,
+        RewardAvailability {
+            is_active: true,
+            is_deleted: false,
+            remaining_slots: Some(0),
+        },
+    );
+    assert!(!availability.is_purchasable());
+}
+
+#[tokio::test]
+async fn reward_availability_is_none_for_unknown_rewards() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(&server, "/v1/extras", vec![vec![common::purchase()]]).await;
+
+    assert_eq!(client.reward_availability(99).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn snapshot_fetches_every_listing() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        vec![vec![common::support()], vec![common::support()]],
+    )
+    .await;
+    mount_pages(&server, "/v1/extras", vec![vec![common::purchase()]]).await;
+    mount_empty(&server, "/v1/subscriptions", "No subscriptions").await;
+
+    let snapshot = client.snapshot().await.unwrap();
+
+    assert!(snapshot.members.is_empty());
+    assert_eq!(snapshot.supporters.len(), 2);
+    assert_eq!(snapshot.extras.len(), 1);
+}
+
+#[tokio::test]
+async fn all_supporters_concurrent_keeps_page_order() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        (1..=5)
+            .map(|id| vec![with_id(common::support(), "support_id", id)])
+            .collect(),
+    )
+    .await;
+
+    let ids: Vec<_> = client
+        .all_supporters_concurrent(3)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|support| support.id)
+        .collect();
+
+    assert_eq!(ids, [1, 2, 3, 4, 5]);
+}
+
+#[tokio::test]
+async fn all_supporters_concurrent_is_empty_without_data() {
+    let (server, client) = common::mock().await;
+
+    mount_empty(&server, "/v1/supporters", "No supporters").await;
+
+    assert!(client
+        .all_supporters_concurrent(3)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn recent_supporters_stops_at_limit() {
+    let (server, client) = common::mock().await;
+
+    for (page, ids) in [(1, [1, 2]), (2, [3, 4]), (3, [5, 6])] {
+        Mock::given(method("GET"))
+            .and(path("/v1/supporters"))
+            .and(query_param("page", page.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(common::page(
+                    page,
+                    3,
+                    ids.map(|id| with_id(common::support(), "support_id", id))
+                        .to_vec(),
+                )),
+            )
+            .expect(if page == 3 { 0 } else { 1 })
+            .mount(&server)
+            .await;
+    }
+
+    let ids: Vec<u32> = client
+        .recent_supporters(3)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|support| support.id)
+        .collect();
+
+    assert_eq!(ids, [1, 2, 3]);
+}
+
+#[tokio::test]
+async fn recent_supporters_returns_fewer_when_exhausted() {
+    let (server, client) = common::mock().await;
+
+    mount_pages(&server, "/v1/supporters", vec![vec![common::support()]]).await;
+
+    assert_eq!(client.recent_supporters(5).await.unwrap().len(), 1);
+
+    let (server, client) = common::mock().await;
+
+    mount_empty(&server, "/v1/supporters", "No supporters").await;
+
+    assert!(client.recent_supporters(5).await.unwrap().is_empty());
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn members_since_stops_at_older_members() {
+    use chrono::{TimeZone, Utc};
+
+    let (server, client) = common::mock().await;
+    let membership = |id, created_on: &str| {
+        let mut membership = with_id(common::membership(), "subscription_id", id);
+
+        membership["subscription_created_on"] = created_on.into();
+        membership
+    };
+
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            3,
+            vec![
+                membership(3, "2024-03-01 00:00:00"),
+                membership(2, "2024-02-01 00:00:00"),
+            ],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            2,
+            3,
+            vec![
+                membership(1, "2024-01-15 00:00:00"),
+                membership(0, "2023-12-01 00:00:00"),
+            ],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ids: Vec<_> = client
+        .members_since(
+            MemberStatus::All,
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|membership| membership.id)
+        .collect();
+
+    assert_eq!(ids, [3, 2, 1]);
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn members_since_scans_everything_when_unordered() {
+    use chrono::{TimeZone, Utc};
+
+    let (server, client) = common::mock().await;
+    let membership = |id, created_on: &str| {
+        let mut membership = with_id(common::membership(), "subscription_id", id);
+
+        membership["subscription_created_on"] = created_on.into();
+        membership
+    };
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![
+            vec![
+                membership(1, "2023-12-01 00:00:00"),
+                membership(2, "2024-02-01 00:00:00"),
+            ],
+            vec![membership(3, "2024-03-01 00:00:00")],
+        ],
+    )
+    .await;
+
+    let ids: Vec<_> = client
+        .members_since(
+            MemberStatus::All,
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|membership| membership.id)
+        .collect();
+
+    assert_eq!(ids, [2, 3]);
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn supporters_between_includes_bounds_and_stops_early() {
+    use chrono::{TimeZone, Utc};
+
+    let (server, client) = common::mock().await;
+    let support = |id, created_on: &str| {
+        let mut support = with_id(common::support(), "support_id", id);
+
+        support["support_created_on"] = created_on.into();
+        support
+    };
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            3,
+            vec![
+                support(4, "2024-03-01 00:00:00"),
+                support(3, "2024-02-01 00:00:00"),
+            ],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            2,
+            3,
+            vec![
+                support(2, "2024-01-01 00:00:00"),
+                support(1, "2023-12-31 23:59:59"),
+            ],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ids: Vec<_> = client
+        .supporters_between(
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap(),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|support| support.id)
+        .collect();
+
+    assert_eq!(ids, [3, 2]);
+}
+
+#[tokio::test]
+async fn unique_payers_normalizes_emails() {
+    let (server, client) = common::mock().await;
+    let mut member = common::membership();
+    let mut shouting = common::support();
+    let mut anonymous = common::support();
+
+    member["payer_email"] = "John@Example.com".into();
+    shouting["payer_email"] = " JOHN@EXAMPLE.COM".into();
+    anonymous["payer_email"] = "".into();
+
+    mount_pages(
+        &server,
+        "/v1/subscriptions",
+        vec![vec![member, common::membership()]],
+    )
+    .await;
+    mount_pages(
+        &server,
+        "/v1/supporters",
+        vec![vec![common::support(), shouting], vec![anonymous]],
+    )
+    .await;
+
+    let mut payers: Vec<_> = client.unique_payers().await.unwrap().into_iter().collect();
+
+    payers.sort();
+
+    assert_eq!(payers, ["jane@example.com", "john@example.com"]);
+}
+
+#[tokio::test]
+async fn list_supporters_sends_params_and_sorts_page() {
+    let (server, client) = common::mock().await;
+    let mut big = with_id(common::support(), "support_id", 2);
+
+    big["support_coffees"] = 5.into();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .and(query_param("page", "2"))
+        .and(query_param("per_page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            2,
+            2,
+            vec![with_id(common::support(), "support_id", 1), big],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let page = client
+        .list_supporters(
+            ListParams::new()
+                .page(2)
+                .per_page(2)
+                .sort(SupportSortKey::Coffees, true),
+        )
+        .await
+        .unwrap();
+    let ids: Vec<_> = page.data.iter().map(|support| support.id).collect();
+
+    assert_eq!(ids, [2, 1]);
+}
+
+#[tokio::test]
+async fn list_supporters_rejects_zero_per_page() {
+    let (_server, client) = common::mock().await;
+
+    assert!(matches!(
+        client.list_supporters(ListParams::new().per_page(0)).await,
+        Err(Error::InvalidConfig(_)),
+    ));
+}
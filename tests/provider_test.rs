@@ -0,0 +1,66 @@
+#![cfg(feature = "async-trait")]
+
+mod common;
+
+use async_trait::async_trait;
+use buy_me_a_coffee::{Error, MemberStatus, Membership, Page, Result, SubscriptionProvider};
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+/// Provides a single membership without any requests.
+struct Fixed(Membership);
+
+#[async_trait]
+impl SubscriptionProvider for Fixed {
+    async fn members(&self, _status: MemberStatus, page: u16) -> Result<Page<Membership>> {
+        let page = common::page(page, 1, vec![serde_json::to_value(&self.0).unwrap()]);
+
+        Ok(serde_json::from_value(page).unwrap())
+    }
+
+    async fn membership(&self, id: u32) -> Result<Membership> {
+        if id == self.0.id {
+            Ok(self.0.clone())
+        } else {
+            Err(Error::Client(reqwest::StatusCode::NOT_FOUND))
+        }
+    }
+}
+
+/// Generic code of the kind the trait is meant for.
+async fn payer_email(provider: &impl SubscriptionProvider, id: u32) -> Result<String> {
+    Ok(provider.membership(id).await?.payer_email)
+}
+
+#[tokio::test]
+async fn mock_provider_stands_in_for_client() {
+    let membership: Membership = serde_json::from_value(common::membership()).unwrap();
+    let provider = Fixed(membership.clone());
+
+    assert_eq!(
+        payer_email(&provider, membership.id).await.unwrap(),
+        membership.payer_email,
+    );
+    assert_eq!(
+        provider.members(MemberStatus::All, 1).await.unwrap().data,
+        [membership],
+    );
+    assert!(payer_email(&provider, 0).await.is_err());
+}
+
+#[tokio::test]
+async fn client_is_a_provider() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/subscriptions/2345"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::membership()))
+        .mount(&server)
+        .await;
+
+    let provider: &dyn SubscriptionProvider = &client;
+
+    assert_eq!(provider.membership(2345).await.unwrap().id, 2345);
+}
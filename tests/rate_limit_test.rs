@@ -0,0 +1,117 @@
+mod common;
+
+use std::time::{Duration, Instant, SystemTime};
+
+use buy_me_a_coffee::{Client, Error};
+use futures::future::try_join_all;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn rate_limited_with_seconds() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "30"))
+        .mount(&server)
+        .await;
+
+    let err = client.supporters(1).await.unwrap_err();
+
+    assert!(
+        matches!(err, Error::RateLimited { retry_after: Some(retry_after) } if retry_after == Duration::from_secs(30)),
+        "{err:?}",
+    );
+}
+
+#[tokio::test]
+async fn rate_limited_with_http_date() {
+    let (server, client) = common::mock().await;
+    let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(120));
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", date.as_str()))
+        .mount(&server)
+        .await;
+
+    let err = client.supporters(1).await.unwrap_err();
+    let Error::RateLimited {
+        retry_after: Some(retry_after),
+    } = err
+    else {
+        panic!("{err:?}");
+    };
+
+    assert!(retry_after > Duration::from_secs(100) && retry_after <= Duration::from_secs(120));
+}
+
+#[tokio::test]
+async fn rate_limited_without_retry_after() {
+    let (server, client) = common::mock().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429))
+        .mount(&server)
+        .await;
+
+    let err = client.supporters(1).await.unwrap_err();
+
+    assert!(
+        matches!(err, Error::RateLimited { retry_after: None }),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn rate_limit_is_shared_between_clones() {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .rate_limit(10)
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .expect(15)
+        .mount(&server)
+        .await;
+
+    let started = Instant::now();
+
+    // The first 10 requests are a burst, each of the other 5 waits 100ms
+    try_join_all((0..15).map(|_| {
+        let client = client.clone();
+
+        async move { client.support(245731).await }
+    }))
+    .await
+    .unwrap();
+
+    assert!(started.elapsed() >= Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn clone_spends_the_same_budget() {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .rate_limit(2)
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .mount(&server)
+        .await;
+
+    client.support(245731).await.unwrap();
+    client.support(245731).await.unwrap();
+
+    let started = Instant::now();
+
+    // The original used up the burst, so the clone has to wait
+    clone.support(245731).await.unwrap();
+
+    assert!(started.elapsed() >= Duration::from_millis(400));
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use std::time::Duration;
+
+use buy_me_a_coffee::{Client, Error};
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+async fn retrying_mock() -> (MockServer, Client) {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .retry(3, Duration::from_millis(10))
+        .build()
+        .unwrap();
+
+    (server, client)
+}
+
+#[tokio::test]
+async fn retries_server_errors() {
+    let (server, client) = retrying_mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .expect(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert_eq!(client.support(245731).await.unwrap().id, 245731);
+}
+
+#[tokio::test]
+async fn with_attempts_counts_retries() {
+    let (server, client) = retrying_mock().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters/245731"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+        .mount(&server)
+        .await;
+
+    let (result, attempts) = client
+        .with_attempts(|client| async move { client.support(245731).await })
+        .await;
+
+    assert_eq!(result.unwrap().id, 245731);
+    assert_eq!(attempts.retries, 2);
+    assert!(attempts.elapsed >= Duration::from_millis(10));
+
+    let (_, attempts) = client
+        .with_attempts(|client| async move { client.support(245731).await })
+        .await;
+
+    assert_eq!(attempts.retries, 0);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_retries() {
+    let (server, client) = retrying_mock().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(502))
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    client.support(245731).await.unwrap_err();
+}
+
+#[tokio::test]
+async fn does_not_retry_client_errors() {
+    let (server, client) = retrying_mock().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client.support(0).await.unwrap_err();
+
+    assert!(matches!(err, Error::Client(status) if status == 404));
+}
+
+#[tokio::test]
+async fn does_not_retry_login_redirect() {
+    let (server, client) = retrying_mock().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw("<html></html>", "text/html"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client.support(0).await.unwrap_err();
+
+    assert!(matches!(err, Error::Unauthorized { redirected: true }));
+}
+
+#[tokio::test]
+async fn retry_after_beyond_max_wait_is_not_waited_for() {
+    let server = MockServer::start().await;
+    let client = Client::builder("test token")
+        .base_url(server.uri())
+        .retry(3, Duration::from_millis(10))
+        .max_retry_wait(Duration::from_secs(1))
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503).insert_header("retry-after", "120"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client.support(245731).await.unwrap_err();
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use tracing_subscriber::fmt::{format::FmtSpan, MakeWriter};
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Captured {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+impl Captured {
+    fn output(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+fn subscriber(captured: &Captured) -> impl tracing::Subscriber {
+    tracing_subscriber::fmt()
+        .with_writer(captured.clone())
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .finish()
+}
+
+#[tokio::test]
+async fn records_request_span() {
+    let (server, client) = common::mock().await;
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured));
+
+    Mock::given(method("GET"))
+        .and(path("/v1/supporters"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::page(
+            1,
+            1,
+            vec![common::support()],
+        )))
+        .mount(&server)
+        .await;
+
+    client.supporters(1).await.unwrap();
+
+    let output = captured.output();
+
+    assert!(output.contains(r#"endpoint="/v1/supporters""#), "{output}");
+    assert!(output.contains(r#"page="1""#), "{output}");
+    assert!(output.contains("status=200"), "{output}");
+    assert!(!output.contains("test token"), "{output}");
+}
+
+#[tokio::test]
+async fn records_error_event() {
+    let (server, client) = common::mock().await;
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured));
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    client.support(0).await.unwrap_err();
+
+    let output = captured.output();
+
+    assert!(output.contains("ERROR"), "{output}");
+    assert!(output.contains("Client(404)"), "{output}");
+    assert!(output.contains("status=404"), "{output}");
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn blocking_records_request_span() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(async {
+        let server = wiremock::MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/supporters/245731"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(common::support()))
+            .mount(&server)
+            .await;
+
+        server
+    });
+    let client = buy_me_a_coffee::blocking::Client::builder("test token")
+        .base_url(server.uri())
+        .build_blocking()
+        .unwrap();
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured));
+
+    client.support(245731).unwrap();
+
+    let output = captured.output();
+
+    assert!(
+        output.contains(r#"endpoint="/v1/supporters/245731""#),
+        "{output}"
+    );
+    assert!(output.contains("status=200"), "{output}");
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use buy_me_a_coffee::{
+    Client, Error, HttpTransport, MemberStatus, ReplayTransport, TransportFuture,
+};
+use futures::future::ready;
+use reqwest::Request;
+
+/// Responds to every request with `body`, recording the URLs requested.
+#[derive(Clone, Default)]
+struct Canned {
+    body: String,
+    urls: Arc<Mutex<Vec<String>>>,
+}
+
+impl HttpTransport for Canned {
+    fn get(&self, request: Request) -> TransportFuture<'_> {
+        self.urls.lock().unwrap().push(request.url().to_string());
+
+        Box::pin(ready(Ok(http::Response::new(self.body.clone()).into())))
+    }
+}
+
+#[tokio::test]
+async fn requests_go_through_transport() {
+    let transport = Canned {
+        body: common::support().to_string(),
+        ..Default::default()
+    };
+    let client = Client::builder("test token")
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    assert_eq!(client.support(245731).await.unwrap().id, 245731);
+    assert_eq!(
+        *transport.urls.lock().unwrap(),
+        ["https://developers.buymeacoffee.com/api/v1/supporters/245731"],
+    );
+}
+
+#[tokio::test]
+async fn replay_reads_responses_from_files() {
+    let dir = std::env::temp_dir().join(format!("buy-me-a-coffee-replay-{}", std::process::id()));
+
+    std::fs::create_dir_all(dir.join("supporters")).unwrap();
+    std::fs::write(
+        dir.join("supporters/245731.json"),
+        common::support().to_string(),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("subscriptions_page-1_status-active.json"),
+        common::page(1, 1, vec![common::membership()]).to_string(),
+    )
+    .unwrap();
+
+    let client = Client::builder("test token")
+        .transport(ReplayTransport::new(&dir))
+        .build()
+        .unwrap();
+
+    assert_eq!(client.support(245731).await.unwrap().id, 245731);
+    assert_eq!(
+        client.members(MemberStatus::Active, 1).await.unwrap().data[0].id,
+        2345,
+    );
+    assert!(matches!(
+        client.support(0).await,
+        Err(Error::Client(status)) if status == 404,
+    ));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn replay_paths_stay_in_directory() {
+    let transport = ReplayTransport::new("fixtures");
+    let path = |url: &str| transport.path(&reqwest::Url::parse(url).unwrap());
+
+    assert_eq!(
+        path("https://example.com/api/v1/supporters?page=../../secret"),
+        std::path::Path::new("fixtures/supporters_page-..%2F..%2Fsecret.json"),
+    );
+    assert_eq!(
+        path("https://example.com/api/v1/supporters?..\\..%5Cpage=1"),
+        std::path::Path::new("fixtures/supporters_..%5C..%5Cpage-1.json"),
+    );
+    assert_eq!(
+        path("https://example.com/api/v1//etc/passwd"),
+        std::path::Path::new("fixtures/%00/etc/passwd.json"),
+    );
+    assert_eq!(
+        path("https://example.com/api/v1/%2Fetc?page=1"),
+        std::path::Path::new("fixtures/%2Fetc_page-1.json"),
+    );
+    assert_eq!(
+        path("https://example.com/api/v1/supporters/245731"),
+        std::path::Path::new("fixtures/supporters/245731.json"),
+    );
+}
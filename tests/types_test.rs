@@ -0,0 +1,795 @@
+mod common;
+
+use buy_me_a_coffee::{
+    DurationType, MemberStatus, Membership, Page, Purchase, ServerError, ServerErrorKind, Snapshot,
+    Support, Visibility,
+};
+use serde_json::json;
+
+#[test]
+fn deserialize_membership() {
+    let membership: Membership = serde_json::from_value(common::membership()).unwrap();
+
+    assert_eq!(membership.id, 2345);
+    assert_eq!(membership.cancelled_on, None);
+}
+
+#[test]
+fn deserialize_support() {
+    let support: Support = serde_json::from_value(common::support()).unwrap();
+
+    assert_eq!(support.id, 245731);
+}
+
+#[test]
+fn ids_are_typed() {
+    let mut value = common::support();
+
+    value["transfer_id"] = "tr_1HFtSpJEtINljGAa".into();
+
+    let support: Support = serde_json::from_value(value).unwrap();
+
+    assert_eq!(
+        support.transaction_id.as_str(),
+        "pi_1HFtSpJEtINljGAa0FNgyR8m"
+    );
+    assert_eq!(
+        support
+            .transfer_id
+            .as_ref()
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("tr_1HFtSpJEtINljGAa"),
+    );
+    assert_eq!(
+        serde_json::to_value(&support).unwrap()["transaction_id"],
+        "pi_1HFtSpJEtINljGAa0FNgyR8m",
+    );
+}
+
+#[test]
+fn redacted_debug_hides_personal_information() {
+    let mut value = common::membership();
+
+    value["subscription_message"] = "Hi, it's Jane!".into();
+    value["phone"] = "555-0100".into();
+
+    let membership: Membership = serde_json::from_value(value).unwrap();
+    let debug = membership.redacted_debug();
+
+    assert!(debug.contains("id: 2345"));
+    assert!(!debug.contains("jane@example.com"));
+    assert!(!debug.contains("Jane Doe"));
+    assert!(!debug.contains("Hi, it's Jane!"));
+    assert!(!debug.contains("555-0100"));
+
+    let support: Support = serde_json::from_value(common::support()).unwrap();
+    let debug = support.redacted_debug();
+
+    assert!(debug.contains("id: 245731"));
+    assert!(!debug.contains("john@example.com"));
+    assert!(!debug.contains("John Doe"));
+    assert!(!debug.contains("Thanks for the good work"));
+
+    let purchase: Purchase = serde_json::from_value(common::purchase()).unwrap();
+    let debug = purchase.redacted_debug();
+
+    assert!(debug.contains("id: 1111"));
+    assert!(!debug.contains("john@example.com"));
+    assert!(!debug.contains("John Doe"));
+}
+
+#[test]
+fn deserialize_purchase() {
+    let purchase: Purchase = serde_json::from_value(common::purchase()).unwrap();
+
+    assert_eq!(purchase.id, 1111);
+    assert_eq!(purchase.extra.id, 25);
+}
+
+#[cfg(not(feature = "chrono"))]
+#[test]
+fn timestamps_are_raw_strings() {
+    let membership: Membership = serde_json::from_value(common::membership()).unwrap();
+
+    assert_eq!(membership.created_on, "2020-09-17 14:41:16");
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn timestamps_are_parsed() {
+    use chrono::{TimeZone, Utc};
+
+    let membership: Membership = serde_json::from_value(common::membership()).unwrap();
+    let purchase: Purchase = serde_json::from_value(common::purchase()).unwrap();
+
+    assert_eq!(
+        membership.current_period_end,
+        Utc.with_ymd_and_hms(2020, 10, 17, 14, 41, 16).unwrap(),
+    );
+    assert_eq!(purchase.extra.deleted_on, None);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn nullable_timestamps_are_parsed() {
+    let mut value = common::membership();
+
+    value["subscription_cancelled_on"] = "2020-10-01 09:00:00".into();
+
+    let membership: Membership = serde_json::from_value(value).unwrap();
+
+    assert!(membership.cancelled_on.is_some());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn invalid_timestamp_fails() {
+    let mut value = common::membership();
+
+    value["subscription_created_on"] = "yesterday".into();
+
+    assert!(serde_json::from_value::<Membership>(value).is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn membership_activity() {
+    let membership =
+        |is_cancelled: bool, is_cancelled_at_period_end: bool, current_period_end: &str| {
+            let mut value = common::membership();
+
+            value["subscription_is_cancelled"] = is_cancelled.into();
+            value["subscription_is_cancelled_at_period_end"] = is_cancelled_at_period_end.into();
+            value["subscription_current_period_end"] = current_period_end.into();
+
+            serde_json::from_value::<Membership>(value).unwrap()
+        };
+
+    assert!(membership(false, false, "2999-01-01 00:00:00").is_active());
+    assert!(membership(true, true, "2999-01-01 00:00:00").is_active());
+    assert!(!membership(true, false, "2999-01-01 00:00:00").is_active());
+    assert!(!membership(false, false, "2020-10-17 14:41:16").is_active());
+    assert!(!membership(true, true, "2020-10-17 14:41:16").is_active());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn membership_expiry() {
+    let membership = |is_cancelled_at_period_end: bool, current_period_end: &str| {
+        let mut value = common::membership();
+
+        value["subscription_is_cancelled"] = is_cancelled_at_period_end.into();
+        value["subscription_is_cancelled_at_period_end"] = is_cancelled_at_period_end.into();
+        value["subscription_current_period_end"] = current_period_end.into();
+
+        serde_json::from_value::<Membership>(value).unwrap()
+    };
+
+    assert!(!membership(false, "2999-01-01 00:00:00").is_expired());
+    assert!(!membership(true, "2999-01-01 00:00:00").is_expired());
+    assert!(membership(false, "2020-10-17 14:41:16").is_expired());
+    assert!(membership(true, "2020-10-17 14:41:16").is_expired());
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn prices_are_parsed() {
+    use rust_decimal::Decimal;
+
+    let mut value = common::membership();
+
+    value["subscription_coffee_num"] = 3.into();
+
+    let membership: Membership = serde_json::from_value(value).unwrap();
+    let purchase: Purchase = serde_json::from_value(common::purchase()).unwrap();
+
+    assert_eq!(membership.coffee_price.value(), Some(Decimal::new(5, 0)));
+    assert_eq!(membership.coffee_price.raw(), "5.0000");
+    assert_eq!(membership.total_price(), Some(Decimal::new(15, 0)));
+    assert_eq!(purchase.amount, Decimal::new(500, 2));
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn empty_and_zero_prices_are_zero() {
+    use rust_decimal::Decimal;
+
+    let mut value = common::support();
+
+    value["support_coffee_price"] = "".into();
+
+    let support: Support = serde_json::from_value(value.clone()).unwrap();
+
+    assert_eq!(support.coffee_price.value(), Some(Decimal::ZERO));
+    assert_eq!(support.total_price(), Some(Decimal::ZERO));
+
+    value["support_coffee_price"] = "0.00".into();
+
+    let support: Support = serde_json::from_value(value).unwrap();
+
+    assert_eq!(support.coffee_price.value(), Some(Decimal::ZERO));
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn garbage_prices_are_kept_raw() {
+    let mut value = common::support();
+
+    value["support_coffee_price"] = "5,00 €".into();
+
+    let support: Support = serde_json::from_value(value).unwrap();
+
+    assert_eq!(support.coffee_price.value(), None);
+    assert_eq!(support.coffee_price.raw(), "5,00 €");
+    assert_eq!(support.total_price(), None);
+    assert_eq!(support.amount_display(), None);
+    assert_eq!(
+        serde_json::to_value(&support).unwrap()["support_coffee_price"],
+        "5,00 €",
+    );
+}
+
+#[test]
+fn empty_optional_fields_are_none() {
+    let mut value = common::support();
+
+    value["support_note"] = "".into();
+    value["transfer_id"] = " ".into();
+    value["referer"] = "".into();
+    value["country"] = "".into();
+    value.as_object_mut().unwrap().remove("supporter_name");
+
+    let support: Support = serde_json::from_value(value).unwrap();
+
+    assert_eq!(support.note, None);
+    assert_eq!(support.transfer_id, None);
+    assert_eq!(support.supporter_name, None);
+    assert_eq!(support.referer, None);
+    assert!(support.country.is_none());
+
+    let mut value = common::membership();
+
+    value["subscription_message"] = "".into();
+    value["subscription_cancelled_on"] = "".into();
+    value.as_object_mut().unwrap().remove("referer");
+    value.as_object_mut().unwrap().remove("country");
+
+    let membership: Membership = serde_json::from_value(value).unwrap();
+
+    assert_eq!(membership.message, None);
+    assert!(membership.cancelled_on.is_none());
+    assert_eq!(membership.referer, None);
+    assert!(membership.country.is_none());
+
+    let mut value = common::purchase();
+
+    value["extra"]["reward_deleted_on"] = "".into();
+
+    let purchase: Purchase = serde_json::from_value(value.clone()).unwrap();
+
+    assert!(purchase.extra.deleted_on.is_none());
+
+    value["extra"]
+        .as_object_mut()
+        .unwrap()
+        .remove("reward_deleted_on");
+
+    let purchase: Purchase = serde_json::from_value(value).unwrap();
+
+    assert!(purchase.extra.deleted_on.is_none());
+}
+
+#[test]
+fn member_status_round_trips() {
+    for (status, string) in [
+        (MemberStatus::Active, "active"),
+        (MemberStatus::Inactive, "inactive"),
+        (MemberStatus::All, "all"),
+    ] {
+        assert_eq!(status.to_string(), string);
+        assert_eq!(string.parse::<MemberStatus>().unwrap(), status);
+        assert_eq!(
+            serde_json::from_value::<MemberStatus>(serde_json::to_value(status).unwrap()).unwrap(),
+            status,
+        );
+    }
+}
+
+#[test]
+fn member_status_ignores_case() {
+    assert_eq!("Active".parse::<MemberStatus>(), Ok(MemberStatus::Active));
+    assert_eq!(
+        " INACTIVE ".parse::<MemberStatus>(),
+        Ok(MemberStatus::Inactive)
+    );
+    assert_eq!("aLl".parse::<MemberStatus>(), Ok(MemberStatus::All));
+}
+
+#[cfg(feature = "clap")]
+#[test]
+fn member_status_is_a_value_enum() {
+    use clap::ValueEnum;
+
+    assert_eq!(
+        MemberStatus::from_str("inactive", false),
+        Ok(MemberStatus::Inactive),
+    );
+    assert_eq!(MemberStatus::from_str("ALL", true), Ok(MemberStatus::All));
+    assert!(MemberStatus::from_str("cancelled", true).is_err());
+}
+
+#[test]
+fn unknown_member_status_fails() {
+    let err = "cancelled".parse::<MemberStatus>().unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        r#"unknown member status "cancelled", expected "active", "inactive", or "all""#,
+    );
+    assert!(serde_json::from_str::<MemberStatus>(r#""cancelled""#).is_err());
+}
+
+#[test]
+fn duration_type_is_parsed() {
+    let mut value = common::membership();
+
+    assert_eq!(
+        serde_json::from_value::<Membership>(value.clone())
+            .unwrap()
+            .duration_type,
+        DurationType::Month,
+    );
+
+    value["subscription_duration_type"] = "year".into();
+
+    assert_eq!(
+        serde_json::from_value::<Membership>(value.clone())
+            .unwrap()
+            .duration_type,
+        DurationType::Year,
+    );
+
+    value["subscription_duration_type"] = "fortnight".into();
+
+    let duration_type = serde_json::from_value::<Membership>(value)
+        .unwrap()
+        .duration_type;
+
+    assert_eq!(duration_type, DurationType::Other("fortnight".to_string()));
+    assert_eq!(duration_type.to_string(), "fortnight");
+    assert_eq!("fortnight".parse(), Ok(duration_type));
+}
+
+#[test]
+fn visibility_is_parsed() {
+    let mut value = common::support();
+
+    for (code, visibility) in [
+        (0, Visibility::Private),
+        (1, Visibility::Public),
+        (7, Visibility::Unknown(7)),
+    ] {
+        value["support_visibility"] = code.into();
+
+        let support: Support = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(support.visibility, visibility);
+        assert_eq!(u8::from(visibility), code);
+    }
+
+    let membership: Membership = serde_json::from_value(common::membership()).unwrap();
+
+    assert_eq!(membership.message_visibility, Visibility::Public);
+}
+
+#[test]
+fn serialization_round_trips() {
+    let membership: Membership = serde_json::from_value(common::membership()).unwrap();
+    let support: Support = serde_json::from_value(common::support()).unwrap();
+    let purchase: Purchase = serde_json::from_value(common::purchase()).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(membership).unwrap(),
+        common::membership()
+    );
+    assert_eq!(serde_json::to_value(support).unwrap(), common::support());
+    assert_eq!(serde_json::to_value(purchase).unwrap(), common::purchase());
+}
+
+#[test]
+fn page_serialization_round_trips() {
+    let value = common::page(2, 3, vec![common::support()]);
+    let page: Page<Support> = serde_json::from_value(value.clone()).unwrap();
+
+    assert_eq!(serde_json::to_value(page).unwrap(), value);
+}
+
+#[test]
+fn page_position() {
+    let page = |current_page: u16, data: Vec<serde_json::Value>| {
+        serde_json::from_value::<Page<Support>>(common::page(current_page, 3, data)).unwrap()
+    };
+    let first = page(1, vec![common::support()]);
+    let middle = page(2, vec![common::support()]);
+    let last = page(3, Vec::new());
+
+    assert!(first.has_next() && !first.has_previous() && !first.is_empty());
+    assert!(middle.has_next() && middle.has_previous() && !middle.is_empty());
+    assert!(!last.has_next() && last.has_previous() && last.is_empty());
+}
+
+#[test]
+fn equal_responses_compare_equal() {
+    use std::collections::HashSet;
+
+    let membership = |value| serde_json::from_value::<Membership>(value).unwrap();
+    let purchases: HashSet<Purchase> = [common::purchase(), common::purchase()]
+        .into_iter()
+        .map(|value| serde_json::from_value(value).unwrap())
+        .collect();
+
+    assert_eq!(
+        membership(common::membership()),
+        membership(common::membership())
+    );
+    assert_ne!(
+        membership(common::membership()),
+        membership(common::with_id(common::membership(), "subscription_id", 1)),
+    );
+    assert_eq!(purchases.len(), 1);
+}
+
+#[test]
+fn note_pinned_is_parsed() {
+    let note_pinned = |value: serde_json::Value| {
+        let mut support = common::support();
+
+        support["support_note_pinned"] = value;
+        serde_json::from_value::<Support>(support).map(|support| support.note_pinned)
+    };
+
+    assert!(!note_pinned(0.into()).unwrap());
+    assert!(note_pinned(1.into()).unwrap());
+    assert!(!note_pinned("0".into()).unwrap());
+    assert!(note_pinned("1".into()).unwrap());
+    assert!(note_pinned(2.into()).is_err());
+    assert!(note_pinned("yes".into()).is_err());
+}
+
+#[test]
+fn flags_accept_every_encoding() {
+    let encodings = [
+        (json!(true), true),
+        (json!(1), true),
+        (json!("1"), true),
+        (json!("true"), true),
+        (json!(false), false),
+        (json!(0), false),
+        (json!("0"), false),
+        (json!("false"), false),
+    ];
+
+    for (encoding, expected) in encodings {
+        let mut membership = common::membership();
+        let mut support = common::support();
+        let mut purchase = common::purchase();
+
+        membership["subscription_is_cancelled"] = encoding.clone();
+        support["is_refunded"] = encoding.clone();
+        purchase["purchase_is_revoked"] = encoding.clone();
+        purchase["extra"]["reward_is_active"] = encoding.clone();
+
+        let membership: Membership = serde_json::from_value(membership).unwrap();
+        let support: Support = serde_json::from_value(support).unwrap();
+        let purchase: Purchase = serde_json::from_value(purchase).unwrap();
+
+        assert_eq!(membership.is_cancelled, expected, "{encoding}");
+        assert_eq!(support.is_refunded, expected, "{encoding}");
+        assert_eq!(purchase.is_revoked, expected, "{encoding}");
+        assert_eq!(purchase.extra.is_active, expected, "{encoding}");
+    }
+}
+
+#[test]
+fn server_error_kinds() {
+    for (reason, kind) in [
+        ("No subscriptions", ServerErrorKind::NoSubscriptions),
+        ("No supporters", ServerErrorKind::NoSupporters),
+        ("No extra purchases", ServerErrorKind::NoExtraPurchases),
+        ("Something else", ServerErrorKind::Other),
+    ] {
+        let err: ServerError =
+            serde_json::from_value(serde_json::json!({ "error": reason })).unwrap();
+
+        assert_eq!(err.kind(), kind);
+    }
+}
+
+#[test]
+fn large_counts_are_parsed() {
+    let mut membership = common::membership();
+    let mut support = common::support();
+    let mut purchase = common::purchase();
+
+    membership["subscription_coffee_num"] = 70000.into();
+    support["support_coffees"] = 256.into();
+    purchase["extra"]["reward_slots"] = 70000.into();
+    purchase["extra"]["reward_used"] = 256.into();
+
+    let membership: Membership = serde_json::from_value(membership).unwrap();
+    let support: Support = serde_json::from_value(support).unwrap();
+    let purchase: Purchase = serde_json::from_value(purchase).unwrap();
+
+    assert_eq!(membership.coffee_num, 70000);
+    assert_eq!(support.coffee_num, 256);
+    assert_eq!(purchase.extra.slots, 70000);
+    assert_eq!(purchase.extra.used, 256);
+}
+
+#[test]
+fn trimmed_message_treats_blank_as_absent() {
+    let membership = |message: Option<&str>| {
+        let mut value = common::membership();
+
+        value["subscription_message"] = message.into();
+        serde_json::from_value::<Membership>(value).unwrap()
+    };
+
+    assert_eq!(
+        membership(Some("  Welcome!\n")).trimmed_message(),
+        Some("Welcome!"),
+    );
+    assert_eq!(membership(Some("")).trimmed_message(), None);
+    assert_eq!(membership(Some(" \t")).trimmed_message(), None);
+    assert_eq!(membership(None).trimmed_message(), None);
+}
+
+#[test]
+fn note_is_public_only_if_visible_and_present() {
+    let support = |visibility: u8, note: Option<&str>| {
+        let mut value = common::support();
+
+        value["support_visibility"] = visibility.into();
+        value["support_note"] = note.into();
+        serde_json::from_value::<Support>(value).unwrap()
+    };
+
+    assert!(support(1, Some("Keep it up!")).is_note_public());
+    assert!(!support(0, Some("Keep it up!")).is_note_public());
+    assert!(!support(2, Some("Keep it up!")).is_note_public());
+    assert!(!support(1, Some("  ")).is_note_public());
+    assert!(!support(1, None).is_note_public());
+}
+
+#[test]
+fn remaining_slots_saturate_and_allow_unlimited() {
+    let extra = |slots: u32, used: u32| {
+        let mut value = common::purchase();
+
+        value["extra"]["reward_slots"] = slots.into();
+        value["extra"]["reward_used"] = used.into();
+        serde_json::from_value::<Purchase>(value).unwrap().extra
+    };
+
+    assert_eq!(extra(10, 7).remaining_slots(), Some(3));
+    assert_eq!(extra(10, 10).remaining_slots(), Some(0));
+    assert_eq!(extra(10, 12).remaining_slots(), Some(0));
+    assert_eq!(extra(0, 5).remaining_slots(), None);
+}
+
+#[test]
+fn flat_purchase_hoists_extra_fields() {
+    let purchase: Purchase = serde_json::from_value(common::purchase()).unwrap();
+    let record = serde_json::to_value(purchase.to_flat_record()).unwrap();
+    let value = common::purchase();
+
+    assert_eq!(record["id"], value["purchase_id"]);
+    assert_eq!(record["created_on"], value["purchased_on"]);
+    assert_eq!(record["payer_email"], value["payer_email"]);
+    assert_eq!(record["extra_id"], value["extra"]["reward_id"]);
+    assert_eq!(record["extra_title"], value["extra"]["reward_title"]);
+    assert!(record
+        .as_object()
+        .unwrap()
+        .values()
+        .all(|field| !field.is_object()));
+}
+
+#[test]
+fn unknown_fields_are_preserved() {
+    let mut value = common::purchase();
+
+    value["purchase_gift"] = true.into();
+    value["extra"]["reward_limit_per_supporter"] = 2.into();
+
+    let purchase: Purchase = serde_json::from_value(value.clone()).unwrap();
+
+    assert_eq!(purchase.extra_fields["purchase_gift"], true);
+    assert_eq!(purchase.extra.extra_fields["reward_limit_per_supporter"], 2);
+    assert!(!purchase.extra_fields.contains_key("purchase_id"));
+    assert_eq!(serde_json::to_value(purchase).unwrap(), value);
+}
+
+#[cfg(feature = "country")]
+#[test]
+fn countries_are_parsed() {
+    use buy_me_a_coffee::Country;
+
+    let membership: Membership = serde_json::from_value(common::membership()).unwrap();
+
+    assert_eq!(membership.country, Some(Country::US));
+    assert_eq!("gb".parse::<Country>().unwrap(), Country::GB);
+    assert_eq!(
+        "Narnia".parse::<Country>().unwrap(),
+        Country::Unknown("Narnia".to_string()),
+    );
+    assert_eq!(
+        serde_json::to_value(Country::Unknown("XX".to_string())).unwrap(),
+        "XX",
+    );
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn images_are_parsed() {
+    use buy_me_a_coffee::Image;
+
+    let mut value = common::purchase();
+    value["extra"]["reward_image"] =
+        "https://cdn.buymeacoffee.com/uploads/rewards/2020-09-17/1/083021_sticker-pack.png@600w_0e.webp"
+            .into();
+    let purchase: Purchase = serde_json::from_value(value).unwrap();
+    let url = purchase.extra.image.url().unwrap();
+
+    assert_eq!(url.host_str(), Some("cdn.buymeacoffee.com"));
+    assert!(url.path().ends_with("@600w_0e.webp"));
+    assert_eq!(
+        "not a url".parse::<Image>().unwrap(),
+        Image::Invalid("not a url".to_string()),
+    );
+    assert_eq!(
+        serde_json::to_value(Image::Invalid(String::new())).unwrap(),
+        "",
+    );
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn referer_hosts_are_extracted() {
+    let referer_host = |referer: Option<&str>| {
+        let mut value = common::support();
+        value["referer"] = referer.into();
+        serde_json::from_value::<Support>(value)
+            .unwrap()
+            .referer_host()
+    };
+
+    assert_eq!(
+        referer_host(Some("https://t.co/AbCdEf1234")).as_deref(),
+        Some("t.co"),
+    );
+    assert_eq!(
+        referer_host(Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42s")).as_deref(),
+        Some("youtube.com"),
+    );
+    assert_eq!(
+        referer_host(Some(" https://Twitter.com/i/web/status/1 ")).as_deref(),
+        Some("twitter.com"),
+    );
+    assert_eq!(
+        referer_host(Some("android-app://com.google.android.gm/")).as_deref(),
+        Some("com.google.android.gm"),
+    );
+    assert_eq!(referer_host(Some("/creator/membership")), None);
+    assert_eq!(referer_host(Some("buymeacoffee.com/creator")), None);
+    assert_eq!(referer_host(Some("https://")), None);
+    assert_eq!(referer_host(Some("")), None);
+    assert_eq!(referer_host(None), None);
+
+    let mut value = common::membership();
+    value["referer"] = "https://www.instagram.com/".into();
+    let membership: Membership = serde_json::from_value(value).unwrap();
+
+    assert_eq!(membership.referer_host().as_deref(), Some("instagram.com"));
+    assert_eq!(
+        membership.referer_url().unwrap().as_str(),
+        "https://www.instagram.com/",
+    );
+}
+
+#[cfg(feature = "currency")]
+#[test]
+fn currencies_are_parsed() {
+    use buy_me_a_coffee::Currency;
+
+    let support: Support = serde_json::from_value(common::support()).unwrap();
+
+    assert_eq!(support.currency, Currency::USD);
+    assert_eq!("jpy".parse::<Currency>().unwrap().minor_units(), Some(0));
+    assert_eq!(
+        "Doubloons".parse::<Currency>().unwrap(),
+        Currency::Unknown("Doubloons".to_string()),
+    );
+}
+
+#[cfg(all(feature = "currency", feature = "rust_decimal"))]
+#[test]
+fn money_is_displayed_in_minor_units() {
+    use buy_me_a_coffee::{Currency, Money};
+
+    let money = |amount: &str, currency: &str| Money {
+        amount: amount.parse().unwrap(),
+        currency: currency.parse().unwrap(),
+    };
+
+    assert_eq!(money("5.0000", "USD").to_string(), "5.00 USD");
+    assert_eq!(money("500.00", "JPY").to_string(), "500 JPY");
+    assert_eq!(money("1.2345", "KWD").to_string(), "1.235 KWD");
+    assert_eq!(money("1.5", "XXY").to_string(), "1.5 XXY");
+    assert_eq!(money("1", "USD").currency, Currency::USD);
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn amounts_are_displayed() {
+    let mut value = common::support();
+
+    value["support_coffees"] = 3.into();
+
+    let support: Support = serde_json::from_value(value.clone()).unwrap();
+
+    assert_eq!(
+        support.amount_display().as_deref(),
+        Some("3 × $5.00 USD = $15.00"),
+    );
+
+    value["support_currency"] = "SEK".into();
+
+    let support: Support = serde_json::from_value(value).unwrap();
+
+    assert_eq!(
+        support.amount_display().as_deref(),
+        Some("3 × 5.00 SEK = 15.00 SEK"),
+    );
+
+    let mut value = common::membership();
+
+    value["subscription_currency"] = "JPY".into();
+    value["subscription_coffee_price"] = "500.0000".into();
+
+    let membership: Membership = serde_json::from_value(value).unwrap();
+
+    assert_eq!(
+        membership.amount_display().as_deref(),
+        Some("1 × ¥500 JPY = ¥500"),
+    );
+}
+
+#[test]
+fn snapshot_diff_reports_refunds_and_cancellations() {
+    let membership: Membership = serde_json::from_value(common::membership()).unwrap();
+    let support: Support = serde_json::from_value(common::support()).unwrap();
+    let previous = Snapshot {
+        members: vec![membership.clone()],
+        supporters: vec![support.clone()],
+        extras: Vec::new(),
+    };
+    let mut current = previous.clone();
+
+    assert!(current.diff(&previous).is_empty());
+
+    current.members[0].is_cancelled_at_period_end = true;
+    current.supporters[0].is_refunded = true;
+    current.supporters.push(Support {
+        id: 1,
+        ..support.clone()
+    });
+
+    let diff = current.diff(&previous);
+
+    assert_eq!(diff.cancelled_members, current.members);
+    assert_eq!(diff.refunded_supporters, [current.supporters[0].clone()]);
+    assert_eq!(diff.added_supporters, [current.supporters[1].clone()]);
+    assert!(previous.diff(&current).is_empty());
+}
@@ -0,0 +1,170 @@
+use buy_me_a_coffee::{
+    webhook::{self, WebhookEvent},
+    Error,
+};
+use serde_json::json;
+
+fn parse(value: serde_json::Value) -> buy_me_a_coffee::Result<WebhookEvent> {
+    webhook::parse_webhook(value.to_string().as_bytes())
+}
+
+fn envelope(event_type: &str, data: serde_json::Value) -> serde_json::Value {
+    json!({
+        "type": event_type,
+        "live_mode": false,
+        "attempt": 1,
+        "created": 1676544557,
+        "event_id": 1,
+        "data": data,
+    })
+}
+
+#[test]
+fn parses_support_event() {
+    let event = parse(envelope(
+        "donation.created",
+        json!({
+            "id": 58,
+            "amount": 5,
+            "object": "payment",
+            "status": "succeeded",
+            "currency": "USD",
+            "refunded": "false",
+            "created_at": 1676544557,
+            "support_note": "Thanks for the good work",
+            "supporter_name": "Jane Doe",
+            "supporter_email": "jane@example.com",
+            "transaction_id": "pi_3Mc51bJEtINljGAa0zVykgUE",
+            "coffee_count": 1,
+        }),
+    ))
+    .unwrap();
+
+    let WebhookEvent::Support(event) = event else {
+        panic!("expected a support event, got {event:?}");
+    };
+
+    assert_eq!(event.event_type, "donation.created");
+    assert_eq!(event.data.id, 58);
+    assert_eq!(event.data.supporter_name, "Jane Doe");
+    assert!(!event.data.refunded);
+}
+
+#[test]
+fn empty_optional_fields_are_none() {
+    let event = parse(envelope(
+        "donation.created",
+        json!({
+            "id": 58,
+            "amount": 5,
+            "status": "succeeded",
+            "currency": "USD",
+            "created_at": 1676544557,
+            "support_note": "",
+            "supporter_name": "Someone",
+            "supporter_email": null,
+        }),
+    ))
+    .unwrap();
+
+    let WebhookEvent::Support(event) = event else {
+        panic!("expected a support event, got {event:?}");
+    };
+
+    assert_eq!(event.data.support_note, None);
+    assert_eq!(event.data.supporter_email, None);
+    assert_eq!(event.data.transaction_id, None);
+}
+
+#[test]
+fn parses_membership_event() {
+    let event = parse(envelope(
+        "membership.started",
+        json!({
+            "id": 2345,
+            "amount": "5.00",
+            "object": "membership",
+            "paused": "false",
+            "status": "active",
+            "canceled": "false",
+            "currency": "USD",
+            "duration_type": "month",
+            "membership_level_id": 12,
+            "membership_level_name": "Early Access",
+            "started_at": 1676544557,
+            "canceled_at": null,
+            "supporter_name": "Jane Doe",
+            "supporter_email": "jane@example.com",
+            "current_period_start": 1676544557,
+            "current_period_end": 1679222957,
+        }),
+    ))
+    .unwrap();
+
+    let WebhookEvent::Membership(event) = event else {
+        panic!("expected a membership event, got {event:?}");
+    };
+
+    assert_eq!(event.data.membership_level_name, "Early Access");
+    assert_eq!(event.data.canceled_at, None);
+}
+
+#[test]
+fn parses_extra_purchase_event() {
+    let event = parse(envelope(
+        "extra_purchase.created",
+        json!({
+            "id": 3,
+            "amount": 10,
+            "status": "succeeded",
+            "currency": "USD",
+            "refunded": false,
+            "created_at": 1676544557,
+            "supporter_name": "Jane Doe",
+            "extras": [{ "id": 25, "title": "Sticker pack", "amount": "10.00", "quantity": 1 }],
+        }),
+    ))
+    .unwrap();
+
+    let WebhookEvent::Extra(event) = event else {
+        panic!("expected an extra purchase event, got {event:?}");
+    };
+
+    assert_eq!(event.data.extras[0].title, "Sticker pack");
+}
+
+#[test]
+fn unknown_events_are_kept_raw() {
+    let event = parse(envelope("recurring_donation.started", json!({ "id": 1 }))).unwrap();
+
+    assert!(matches!(event, WebhookEvent::Other(event) if event.data["id"] == 1));
+}
+
+#[test]
+fn malformed_webhook_fails() {
+    let err = parse(envelope("donation.created", json!({ "id": "nope" }))).unwrap_err();
+
+    assert!(matches!(err, Error::Deserialize { .. }), "{err:?}");
+    assert!(matches!(
+        webhook::parse_webhook(b"not json"),
+        Err(Error::Deserialize { .. }),
+    ));
+}
+
+#[cfg(feature = "ring")]
+#[test]
+fn verifies_signatures() {
+    let body = br#"{"type":"donation.created"}"#;
+    let signature = "0e23d44930da1a5da0c0ea417355f2e54812423e3a07e2fd4c6593c8fe312ab5";
+
+    assert!(webhook::verify_signature("whsec_test", body, signature));
+    assert!(webhook::verify_signature(
+        "whsec_test",
+        body,
+        &signature.to_uppercase(),
+    ));
+    assert!(!webhook::verify_signature("wrong secret", body, signature));
+    assert!(!webhook::verify_signature("whsec_test", b"{}", signature));
+    assert!(!webhook::verify_signature("whsec_test", body, "not hex"));
+    assert!(!webhook::verify_signature("whsec_test", body, "é"));
+}